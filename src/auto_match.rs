@@ -0,0 +1,193 @@
+//! Matches Tandoor foods without FDC ID to FDC foods by their name.
+//!
+//! The FDC database is searched for the name of the food. Every result is scored by how similar its description
+//! is to the name and by the preference of its data type. The best result is linked if its score reaches the
+//! threshold, otherwise the best results are handed back so they can be reviewed.
+use std::error::Error;
+use std::sync::Arc;
+use log::trace;
+use crate::fdc_client::FdcClient;
+use crate::models::match_candidate::MatchCandidate;
+use crate::models::usda::usda_data_type::USDADataType;
+use crate::models::usda::usda_search_request::USDASearchRequest;
+use crate::models::usda::usda_search_result::USDASearchResult;
+
+/// How many results of the search are scored.
+const SEARCH_PAGE_SIZE: u32 = 25;
+/// How many candidates are kept for a review.
+const MAX_CANDIDATES: usize = 5;
+/// How much the score is lowered for every data type preferred over the data type of a result.
+const DATA_TYPE_PENALTY: f32 = 0.05;
+/// How similar two words must be to count as the same word (e.g. singular and plural).
+const MIN_WORD_SIMILARITY: f64 = 0.85;
+/// How much covering the words of the food name counts compared to covering the words of the description.
+const NAME_COVERAGE_WEIGHT: f32 = 0.7;
+
+/// The result of matching a single food.
+pub enum MatchResult {
+    /// The best candidate reached the threshold.
+    Matched(MatchCandidate),
+    /// No candidate reached the threshold. Holds the best candidates, best first.
+    Unsure(Vec<MatchCandidate>),
+}
+
+/// Matches foods by searching the FDC database.
+pub struct AutoMatcher {
+    fdc_client: Arc<FdcClient>,
+    threshold: f32,
+    data_types: Vec<USDADataType>,
+}
+
+impl AutoMatcher {
+    /// Creates a matcher.
+    /// ### Parameters
+    /// - fdc_client: The client used to search the FDC database.
+    /// - threshold: The score between 0 and 1 the best candidate needs to be linked automatically.
+    /// - data_types: The data types that are searched, most preferred first.
+    pub fn new(fdc_client: Arc<FdcClient>, threshold: f32, data_types: Vec<USDADataType>) -> Self {
+        AutoMatcher { fdc_client, threshold, data_types }
+    }
+
+    /// Finds the FDC food matching a food name.
+    /// ### Parameters
+    /// - food_name: The name of the food in Tandoor.
+    /// ### Returns
+    /// The matched food, the best candidates if none is good enough or an error if the search failed.
+    /// ### Remarks
+    /// Candidates sharing the best score are ambiguous and never linked automatically.
+    pub async fn find(&self, food_name: &str) -> Result<MatchResult, Box<dyn Error>> {
+        let request = USDASearchRequest {
+            query: food_name.to_string(),
+            data_type: (!self.data_types.is_empty()).then(|| self.data_types.iter().map(|d| d.api_name().to_string()).collect()),
+            page_size: SEARCH_PAGE_SIZE,
+            page_number: 1,
+        };
+        let response = self.fdc_client.search(&request).await?;
+
+        let mut candidates: Vec<MatchCandidate> = response.foods
+            .iter()
+            .map(|result| MatchCandidate::new(result, self.score(food_name, result)))
+            .collect();
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates.truncate(MAX_CANDIDATES);
+        trace!("Candidates for {}: {:?}", food_name, candidates);
+        Ok(best_match(candidates, self.threshold))
+    }
+
+    /// Scores how well a search result matches a food name.
+    fn score(&self, food_name: &str, result: &USDASearchResult) -> f32 {
+        let rank = self.data_types
+            .iter()
+            .position(|d| result.data_type.as_deref() == Some(d.api_name()))
+            .unwrap_or(self.data_types.len());
+        (similarity(food_name, &result.description) - DATA_TYPE_PENALTY * rank as f32).max(0.0)
+    }
+}
+
+/// Decides whether the best of the candidates is linked.
+/// ### Parameters
+/// - candidates: The scored candidates, best first.
+/// - threshold: The score the best candidate needs to be linked.
+/// ### Returns
+/// The best candidate if it reaches the threshold and no other candidate has the same score, otherwise all candidates.
+fn best_match(candidates: Vec<MatchCandidate>, threshold: f32) -> MatchResult {
+    let is_ambiguous = candidates.len() > 1 && candidates[0].score == candidates[1].score;
+    match candidates.first() {
+        Some(best) if best.score >= threshold && !is_ambiguous => MatchResult::Matched(best.clone()),
+        _ => MatchResult::Unsure(candidates),
+    }
+}
+
+/// Calculates how similar a food name and an FDC description are between 0 and 1.
+/// ### Remarks
+/// FDC descriptions list the words in a different order than people do (e.g. "Syrups, maple" for "Maple syrup"),
+/// so the words are compared independent of their order. A description matches well if it contains all words of the
+/// name; additional words in the description (e.g. "salted") lower the score slightly.
+fn similarity(food_name: &str, description: &str) -> f32 {
+    let name_words = words(food_name);
+    let description_words = words(description);
+    if name_words.is_empty() || description_words.is_empty() {
+        return 0.0;
+    }
+    NAME_COVERAGE_WEIGHT * coverage(&name_words, &description_words)
+        + (1.0 - NAME_COVERAGE_WEIGHT) * coverage(&description_words, &name_words)
+}
+
+/// Calculates which share of the words is found among the other words.
+fn coverage(words: &[String], other_words: &[String]) -> f32 {
+    let found: f64 = words
+        .iter()
+        .map(|word| other_words
+            .iter()
+            .map(|other| strsim::jaro_winkler(word, other))
+            .filter(|similarity| *similarity >= MIN_WORD_SIMILARITY)
+            .fold(0.0, f64::max))
+        .sum();
+    (found / words.len() as f64) as f32
+}
+
+/// Splits a text into lowercase words.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The default threshold of the appsettings.
+    const THRESHOLD: f32 = 0.85;
+
+    fn candidate(fdc_id: i32, description: &str, score: f32) -> MatchCandidate {
+        MatchCandidate { fdc_id, description: description.to_string(), data_type: None, brand: None, score }
+    }
+
+    #[test]
+    fn reordered_and_plural_words_match() {
+        assert!(similarity("Maple syrup", "Syrups, maple") >= THRESHOLD);
+    }
+
+    #[test]
+    fn additional_words_lower_the_score() {
+        let exact = similarity("Butter", "Butter");
+        let salted = similarity("Butter", "Butter, salted");
+        assert!(salted < exact);
+        assert!(similarity("Butter", "Butter, salted, whipped") < salted);
+    }
+
+    #[test]
+    fn words_of_the_name_count_more_than_words_of_the_description() {
+        let names = words("Maple syrup");
+        let descriptions = words("Syrup");
+        assert_eq!(coverage(&descriptions, &names), 1.0);
+        assert!((coverage(&names, &descriptions) - 0.5).abs() < 1e-6);
+        assert!(similarity("Maple syrup", "Syrup") < similarity("Syrup", "Maple syrup"));
+    }
+
+    #[test]
+    fn unrelated_descriptions_score_zero() {
+        assert_eq!(similarity("Butter", "Apples, raw"), 0.0);
+        assert_eq!(similarity("", "Butter"), 0.0);
+    }
+
+    #[test]
+    fn the_best_candidate_above_the_threshold_is_linked() {
+        let candidates = vec![candidate(1, "Butter", 0.95), candidate(2, "Butter, salted", 0.9)];
+        assert!(matches!(best_match(candidates, THRESHOLD), MatchResult::Matched(best) if best.fdc_id == 1));
+    }
+
+    #[test]
+    fn tied_best_candidates_are_ambiguous() {
+        let candidates = vec![candidate(1, "Butter, salted", 0.95), candidate(2, "Butter, without salt", 0.95)];
+        assert!(matches!(best_match(candidates, THRESHOLD), MatchResult::Unsure(candidates) if candidates.len() == 2));
+    }
+
+    #[test]
+    fn candidates_below_the_threshold_are_not_linked() {
+        let candidates = vec![candidate(1, "Butter, salted", 0.8)];
+        assert!(matches!(best_match(candidates, THRESHOLD), MatchResult::Unsure(_)));
+    }
+}
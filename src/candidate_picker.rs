@@ -0,0 +1,223 @@
+//! Lets the user pick the FDC food of a Tandoor food from search results.
+use std::io;
+use std::sync::Arc;
+use log::{trace, warn};
+use crate::commands::search::to_table;
+use crate::fdc_client::FdcClient;
+use crate::fdc_id::parse_fdc_url;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+use crate::models::usda::usda_data_type::USDADataType;
+use crate::models::usda::usda_search_request::USDASearchRequest;
+use crate::models::usda::usda_search_result::USDASearchResult;
+
+/// How many candidates are listed.
+const MAX_CANDIDATES: u32 = 10;
+
+/// The minimum number of digits of an FDC ID entered without the `id:` prefix, so mistyped candidate numbers are not
+/// taken as FDC IDs.
+const MIN_FDC_ID_DIGITS: usize = 6;
+
+/// The prefix marking an entered number as FDC ID.
+const FDC_ID_PREFIX: &str = "id:";
+
+/// What the user decided for a food.
+pub enum PickResult {
+    /// The FDC ID the user picked.
+    Picked(i32),
+    /// The user skipped the food for this run.
+    Skipped,
+    /// The user never wants to be asked about the food again.
+    Ignored,
+}
+
+/// How a response of the user is interpreted.
+enum Response {
+    /// The user decided about the food.
+    Decision(PickResult),
+    /// The response is a new search.
+    Search,
+    /// The response is a number that is neither a listed candidate nor an FDC ID.
+    OutOfRange(usize),
+}
+
+/// Asks the user to pick the FDC food of Tandoor foods.
+pub struct CandidatePicker {
+    fdc_client: Arc<FdcClient>,
+    data_types: Vec<USDADataType>,
+}
+
+impl CandidatePicker {
+    /// Creates a picker.
+    /// ### Parameters
+    /// - fdc_client: The client used to search the FDC database.
+    /// - data_types: The data types that are searched.
+    pub fn new(fdc_client: Arc<FdcClient>, data_types: Vec<USDADataType>) -> Self {
+        CandidatePicker { fdc_client, data_types }
+    }
+
+    /// Searches the FDC database for the name of the food and asks the user to pick one of the candidates.
+    /// ### Parameters
+    /// - food: The food for which the FDC ID should be retrieved.
+    /// ### Returns
+    /// The decision of the user.
+    /// ### Remarks
+    /// The user can pick a candidate by its number, paste an FDC URL or ID, skip the food or enter any other text
+    /// to search again with it. Numbers that are neither a candidate nor an FDC ID are rejected and the user is asked again.
+    pub async fn pick(&self, food: &InternalTandoorFood) -> PickResult {
+        let mut query = food.name.to_string();
+        loop {
+            let candidates = self.search(&query).await;
+            println!("\n===== {} (ID {}) =====", food.name, food.id);
+            println!("Candidates for \"{}\":", query);
+            println!("{}", to_table(&candidates, true));
+            loop {
+                println!("Pick a number, paste an FDC URL or ID (e.g. {}169661), enter a new search, leave empty to skip {} or enter \"!\" to never be asked about it again.", FDC_ID_PREFIX, food.name);
+
+                let response = read_line().await;
+                match parse_response(&response, &candidates) {
+                    Response::Decision(result) => return result,
+                    Response::Search => {
+                        query = response;
+                        break;
+                    }
+                    Response::OutOfRange(number) if candidates.is_empty() => {
+                        println!("There is no candidate {}. Enter an FDC ID with at least {} digits or a new search.", number, MIN_FDC_ID_DIGITS);
+                    }
+                    Response::OutOfRange(number) => {
+                        println!("There is no candidate {}. Enter a number from 1 to {} or an FDC ID with at least {} digits.", number, candidates.len(), MIN_FDC_ID_DIGITS);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Searches the FDC database. Errors are reported and treated as no results, so the user can try again.
+    async fn search(&self, query: &str) -> Vec<USDASearchResult> {
+        let request = USDASearchRequest {
+            query: query.to_string(),
+            data_type: (!self.data_types.is_empty()).then(|| self.data_types.iter().map(|d| d.api_name().to_string()).collect()),
+            page_size: MAX_CANDIDATES,
+            page_number: 1,
+        };
+        match self.fdc_client.search(&request).await {
+            Ok(response) => response.foods,
+            Err(e) => {
+                warn!("Error searching the FDC database for {}: {:?}", query, e);
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// Interprets the response of the user.
+/// ### Parameters
+/// - response: The trimmed response.
+/// - candidates: The listed candidates.
+/// ### Returns
+/// How the response is interpreted.
+/// ### Remarks
+/// Numbers up to the number of candidates pick a candidate. Other numbers are only taken as FDC IDs if they have at
+/// least [MIN_FDC_ID_DIGITS] digits or are prefixed with [FDC_ID_PREFIX].
+fn parse_response(response: &str, candidates: &[USDASearchResult]) -> Response {
+    if response.is_empty() {
+        return Response::Decision(PickResult::Skipped);
+    }
+    if response == "!" {
+        return Response::Decision(PickResult::Ignored);
+    }
+    if let Some(fdc_id) = parse_fdc_url(response) {
+        trace!("Got FDC ID {} from a pasted URL.", fdc_id);
+        return Response::Decision(PickResult::Picked(fdc_id));
+    }
+    if let Some(fdc_id) = response.strip_prefix(FDC_ID_PREFIX).and_then(|id| id.trim().parse::<i32>().ok()) {
+        trace!("Got FDC ID {} from user input.", fdc_id);
+        return Response::Decision(PickResult::Picked(fdc_id));
+    }
+    match response.parse::<usize>() {
+        Ok(number) if (1..=candidates.len()).contains(&number) => Response::Decision(PickResult::Picked(candidates[number - 1].fdc_id)),
+        Ok(number) if response.len() >= MIN_FDC_ID_DIGITS => match i32::try_from(number) {
+            Ok(fdc_id) => {
+                trace!("Got FDC ID {} from user input.", fdc_id);
+                Response::Decision(PickResult::Picked(fdc_id))
+            }
+            Err(_) => Response::OutOfRange(number),
+        },
+        Ok(number) => Response::OutOfRange(number),
+        Err(_) => Response::Search,
+    }
+}
+
+/// Reads a line from stdin without the trailing whitespace.
+/// ### Remarks
+/// The line is read on a blocking thread so the runtime workers are not stalled. If stdin cannot be read
+/// (e.g. it is closed) an empty line is returned, which skips the food.
+async fn read_line() -> String {
+    let line = tokio::task::spawn_blocking(|| {
+        let mut response = String::new();
+        io::stdin().read_line(&mut response).map(|_| response)
+    }).await;
+    match line {
+        Ok(Ok(response)) => response.trim().to_string(),
+        Ok(Err(e)) => {
+            warn!("Error reading from stdin: {:?}", e);
+            String::new()
+        }
+        Err(e) => {
+            warn!("Error reading from stdin: {:?}", e);
+            String::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(fdc_id: i32) -> USDASearchResult {
+        USDASearchResult {
+            fdc_id,
+            description: format!("Food {}", fdc_id),
+            data_type: None,
+            brand_owner: None,
+            brand_name: None,
+            food_nutrients: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn numbers_pick_candidates() {
+        let candidates = [candidate(111111), candidate(222222)];
+        assert!(matches!(parse_response("2", &candidates), Response::Decision(PickResult::Picked(222222))));
+    }
+
+    #[test]
+    fn out_of_range_numbers_are_rejected() {
+        let candidates = [candidate(111111), candidate(222222)];
+        assert!(matches!(parse_response("3", &candidates), Response::OutOfRange(3)));
+        assert!(matches!(parse_response("0", &candidates), Response::OutOfRange(0)));
+        assert!(matches!(parse_response("12345", &candidates), Response::OutOfRange(12345)));
+    }
+
+    #[test]
+    fn long_or_prefixed_numbers_are_fdc_ids() {
+        let candidates = [candidate(111111)];
+        assert!(matches!(parse_response("169661", &candidates), Response::Decision(PickResult::Picked(169661))));
+        assert!(matches!(parse_response("id:12345", &candidates), Response::Decision(PickResult::Picked(12345))));
+    }
+
+    #[test]
+    fn urls_are_fdc_ids() {
+        assert!(matches!(parse_response("https://fdc.nal.usda.gov/fdc-app.html#/food-details/169661/nutrients", &[]), Response::Decision(PickResult::Picked(169661))));
+    }
+
+    #[test]
+    fn empty_responses_skip_and_exclamation_marks_ignore() {
+        assert!(matches!(parse_response("", &[]), Response::Decision(PickResult::Skipped)));
+        assert!(matches!(parse_response("!", &[]), Response::Decision(PickResult::Ignored)));
+    }
+
+    #[test]
+    fn other_text_is_a_new_search() {
+        assert!(matches!(parse_response("maple syrup", &[]), Response::Search));
+    }
+}
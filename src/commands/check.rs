@@ -0,0 +1,94 @@
+//! Checks the configuration and the connections and reports foods that cannot be updated.
+use std::process::ExitCode;
+use log::error;
+use crate::commands::{build_client, connect_tandoor, create_retry_policies, load_food_mapping};
+use crate::fdc_client::check_api_key;
+use crate::fdc_id::resolve_fdc_id;
+use crate::models::configuration::Configuration;
+
+/// Runs the check command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// ### Returns
+/// The exit code of the program, failure if any problem was found.
+/// ### Remarks
+/// Foods and properties without FDC ID are reported but are no problem as they are simply not updated.
+pub async fn run(configuration: &Configuration) -> ExitCode {
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut problems = 0;
+
+    match check_api_key(&client, &configuration.usda_api_key).await {
+        Ok(_) => println!("FDC: The API key is valid."),
+        Err(e) => {
+            println!("FDC: {}", e);
+            problems += 1;
+        }
+    }
+
+    let food_mapping = match load_food_mapping(configuration) {
+        Ok(food_mapping) => food_mapping,
+        Err(e) => {
+            println!("Mapping file: {}", e);
+            problems += 1;
+            None
+        }
+    };
+
+    let (tandoor_retry_policy, _) = create_retry_policies(configuration);
+    let tandoor_client = match connect_tandoor(configuration, &client, configuration.tandoor_concurrency, tandoor_retry_policy).await {
+        Ok(tandoor_client) => tandoor_client,
+        Err(e) => {
+            println!("Tandoor: Error connecting to {}: {}", configuration.tandoor_url, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Tandoor: Connected to {} using API {}.", tandoor_client.base_url(), tandoor_client.version());
+
+    match tandoor_client.get_properties().await {
+        Ok(properties) => {
+            let without_fdc_id: Vec<&str> = properties.iter().filter(|p| p.fdc_id.is_none()).map(|p| p.name.as_str()).collect();
+            println!("Properties: {} found, {} without FDC ID.", properties.len(), without_fdc_id.len());
+            for name in without_fdc_id {
+                println!("  {} has no FDC ID and is never updated.", name);
+            }
+        }
+        Err(e) => {
+            println!("Properties: Error loading the properties: {}", e);
+            problems += 1;
+        }
+    }
+
+    match tandoor_client.get_foods(None).await {
+        Ok(loaded_foods) => {
+            let without_fdc_id: Vec<&str> = loaded_foods.foods.iter().filter(|f| resolve_fdc_id(f, food_mapping.as_ref(), configuration.mapping_precedence).is_none()).map(|f| f.name.as_str()).collect();
+            println!("Foods: {} found, {} without FDC ID, {} unreadable.", loaded_foods.foods.len(), without_fdc_id.len(), loaded_foods.skipped.len());
+            for name in without_fdc_id {
+                println!("  {} has no FDC ID.", name);
+            }
+            for skipped in &loaded_foods.skipped {
+                println!("  {} could not be read: {}", skipped, skipped.reason);
+            }
+            if !loaded_foods.skipped.is_empty() {
+                problems += 1;
+            }
+        }
+        Err(e) => {
+            println!("Foods: Error loading the foods: {}", e);
+            problems += 1;
+        }
+    }
+
+    if problems > 0 {
+        println!("{} problems found.", problems);
+        ExitCode::FAILURE
+    } else {
+        println!("No problems found.");
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,114 @@
+//! Exports all foods with their properties.
+use std::error::Error;
+use std::fs;
+use std::process::ExitCode;
+use log::{error, info, warn};
+use crate::commands::{build_client, connect_tandoor, create_retry_policies};
+use crate::models::command_line_arguments::{ExportArgs, ExportFormat};
+use crate::models::configuration::Configuration;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+use crate::models::tandoor::internal_tandoor_property::InternalTandoorProperty;
+
+/// Runs the export command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - args: The options of the export command.
+/// ### Returns
+/// The exit code of the program.
+pub async fn run(configuration: &Configuration, args: &ExportArgs) -> ExitCode {
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (tandoor_retry_policy, _) = create_retry_policies(configuration);
+    let tandoor_client = match connect_tandoor(configuration, &client, configuration.tandoor_concurrency, tandoor_retry_policy).await {
+        Ok(tandoor_client) => tandoor_client,
+        Err(e) => {
+            error!("Error connecting to Tandoor: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let properties = match tandoor_client.get_properties().await {
+        Ok(properties) => properties,
+        Err(e) => {
+            error!("Error fetching food properties: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let loaded_foods = match tandoor_client.get_foods(None).await {
+        Ok(loaded_foods) => loaded_foods,
+        Err(e) => {
+            error!("Error fetching foods: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    for skipped in &loaded_foods.skipped {
+        warn!("Food {} could not be loaded and will not be exported: {}", skipped, skipped.reason);
+    }
+
+    let content = match args.format {
+        ExportFormat::Json => serde_json::to_string_pretty(&loaded_foods.foods).map_err(Box::<dyn Error>::from),
+        ExportFormat::Csv => Ok(to_csv(&loaded_foods.foods, &properties)),
+    };
+    let result = content.and_then(|content| match &args.output {
+        Some(output) => fs::write(output, content).map_err(Box::<dyn Error>::from),
+        None => {
+            println!("{}", content);
+            Ok(())
+        }
+    });
+
+    match result {
+        Ok(_) => {
+            if let Some(output) = &args.output {
+                info!("Exported {} foods to {}", loaded_foods.foods.len(), output.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            error!("Error exporting the foods: {:?}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Renders the foods as CSV with one row per food and one column per property.
+/// ### Parameters
+/// - foods: The foods to render.
+/// - properties: All properties of the Tandoor instance. A cell stays empty if the food does not have the property.
+fn to_csv(foods: &[InternalTandoorFood], properties: &[InternalTandoorProperty]) -> String {
+    let mut header = vec!["id".to_string(), "name".to_string(), "fdc_id".to_string(), "url".to_string()];
+    header.extend(properties.iter().map(|p| escape_csv(&p.name)));
+    let mut csv = header.join(",");
+    csv.push('\n');
+
+    for food in foods {
+        let mut row = vec![
+            food.id.to_string(),
+            escape_csv(&food.name),
+            food.fdc_id.map_or(String::new(), |id| id.to_string()),
+            escape_csv(food.url.as_deref().unwrap_or_default()),
+        ];
+        row.extend(properties.iter().map(|property| food.properties
+            .iter()
+            .find(|p| p.property_type.name == property.name)
+            .and_then(|p| p.property_amount)
+            .map_or(String::new(), |a| a.to_string())));
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV value if it contains characters with a special meaning.
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,64 @@
+//! Exports the FDC IDs of all foods to a mapping file.
+use std::path::Path;
+use std::process::ExitCode;
+use log::{error, info, warn};
+use crate::commands::{build_client, connect_tandoor, create_retry_policies};
+use crate::fdc_id::get_fdc_id;
+use crate::food_mapping::write_entries;
+use crate::models::configuration::Configuration;
+use crate::models::food_mapping_entry::FoodMappingEntry;
+
+/// Runs the export-mapping command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - output: The mapping file to write.
+/// ### Returns
+/// The exit code of the program.
+/// ### Remarks
+/// Only the FDC IDs stored in Tandoor are exported. Foods are keyed by their ID and their name is added for readability.
+pub async fn run(configuration: &Configuration, output: &Path) -> ExitCode {
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (tandoor_retry_policy, _) = create_retry_policies(configuration);
+    let tandoor_client = match connect_tandoor(configuration, &client, configuration.tandoor_concurrency, tandoor_retry_policy).await {
+        Ok(tandoor_client) => tandoor_client,
+        Err(e) => {
+            error!("Error connecting to Tandoor: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let loaded_foods = match tandoor_client.get_foods(None).await {
+        Ok(loaded_foods) => loaded_foods,
+        Err(e) => {
+            error!("Error fetching foods: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    for skipped in &loaded_foods.skipped {
+        warn!("Food {} could not be loaded and will not be exported: {}", skipped, skipped.reason);
+    }
+
+    let entries: Vec<FoodMappingEntry> = loaded_foods.foods
+        .iter()
+        .filter_map(|food| get_fdc_id(food).map(|fdc_id| FoodMappingEntry {
+            food_id: Some(food.id),
+            food_name: Some(food.name.to_string()),
+            fdc_id,
+        }))
+        .collect();
+    match write_entries(output, &entries) {
+        Ok(_) => {
+            info!("Exported the FDC IDs of {} foods to {}", entries.len(), output.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            error!("Error writing the mapping file {}: {:?}", output.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Creates the property types of a preset that are missing in Tandoor.
+use std::process::ExitCode;
+use log::{error, info, warn};
+use crate::commands::{build_client, connect_tandoor, create_retry_policies};
+use crate::models::command_line_arguments::InitPropertiesArgs;
+use crate::models::configuration::Configuration;
+use crate::models::tandoor::api_tandoor_property_request::ApiTandoorPropertyRequest;
+use crate::property_presets::properties_of;
+
+/// Runs the init-properties command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - args: The options of the init-properties command.
+/// ### Returns
+/// The exit code of the program.
+/// ### Remarks
+/// A property type of the preset is skipped if Tandoor already has a property type with the same FDC ID or name,
+/// so the command can be run again or with another preset without creating duplicates.
+pub async fn run(configuration: &Configuration, args: &InitPropertiesArgs) -> ExitCode {
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (tandoor_retry_policy, _) = create_retry_policies(configuration);
+    let tandoor_client = match connect_tandoor(configuration, &client, configuration.tandoor_concurrency, tandoor_retry_policy).await {
+        Ok(tandoor_client) => tandoor_client,
+        Err(e) => {
+            error!("Error connecting to Tandoor: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let present_properties = match tandoor_client.get_properties().await {
+        Ok(properties) => properties,
+        Err(e) => {
+            error!("Error fetching food properties: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut created_properties = 0;
+    let mut present_property_number = 0;
+    let mut not_created_properties = 0;
+    for (order, preset_property) in properties_of(args.preset).into_iter().enumerate() {
+        let name = preset_property.name(args.language);
+        if let Some(present) = present_properties.iter().find(|p| p.fdc_id == Some(preset_property.fdc_id)) {
+            info!("Skipping {} as property {} already has FDC ID {}.", name, present.name, preset_property.fdc_id);
+            present_property_number += 1;
+            continue;
+        }
+        if let Some(present) = present_properties.iter().find(|p| p.name.eq_ignore_ascii_case(name)) {
+            warn!("Skipping {} as property {} already exists. Set its FDC ID to {} to import it.", name, present.name, preset_property.fdc_id);
+            present_property_number += 1;
+            continue;
+        }
+
+        let property = ApiTandoorPropertyRequest {
+            name: name.to_string(),
+            unit: preset_property.unit.to_string(),
+            fdc_id: preset_property.fdc_id,
+            order: i32::try_from(order).unwrap_or(i32::MAX),
+        };
+        if args.dry_run {
+            info!("Would create property {} in {} with FDC ID {}", property.name, property.unit, property.fdc_id);
+            created_properties += 1;
+            continue;
+        }
+        match tandoor_client.create_property(&property).await {
+            Ok(_) => {
+                info!("Created property {} in {} with FDC ID {}", property.name, property.unit, property.fdc_id);
+                created_properties += 1;
+            }
+            Err(e) => {
+                warn!("Error creating property {}: {:?}", property.name, e);
+                not_created_properties += 1;
+            }
+        }
+    }
+
+    if args.dry_run {
+        info!("Dry run: No property was created in Tandoor. Properties counted as created would have been created.");
+    }
+    info!("\n {} properties successfully created. \n {} properties were already present. \n {} properties were not created successfully.",
+        created_properties,
+        present_property_number,
+        not_created_properties);
+    if not_created_properties > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
@@ -0,0 +1,120 @@
+//! The commands of the program and the helpers shared between them.
+pub mod check;
+pub mod export;
+pub mod export_mapping;
+pub mod init_properties;
+pub mod prune_cache;
+pub mod restore;
+pub mod search;
+pub mod show;
+pub mod sync;
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use log::{debug, warn};
+use reqwest::{Certificate, Client, Identity, StatusCode};
+use serde::Serialize;
+use crate::fdc_cache::FdcCache;
+use crate::food_mapping::FoodMapping;
+use crate::models::configuration::Configuration;
+use crate::retry::RetryPolicy;
+use crate::tandoor_client::TandoorClient;
+
+/// Builds the client used for all http requests honouring the TLS options of the configuration.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// ### Returns
+/// The configured client or an error if a certificate could not be loaded.
+pub fn build_client(configuration: &Configuration) -> Result<Client, Box<dyn Error>> {
+    let mut builder = Client::builder();
+
+    if let Some(ca_certificate_path) = &configuration.tls_ca_certificate {
+        debug!("Trusting CA certificates from {}", ca_certificate_path);
+        let certificates = Certificate::from_pem_bundle(&fs::read(ca_certificate_path)?)?;
+        builder = builder.tls_certs_merge(certificates);
+    }
+
+    if let Some(client_certificate_path) = &configuration.tls_client_certificate {
+        debug!("Using client certificate from {}", client_certificate_path);
+        builder = builder.identity(Identity::from_pem(&fs::read(client_certificate_path)?)?);
+    }
+
+    if configuration.tls_accept_invalid_certificates {
+        warn!("Invalid TLS certificates are accepted. Connections are not secure.");
+        builder = builder.tls_danger_accept_invalid_certs(true);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Creates the cache for FDC responses as configured.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - refresh: When set, cached entries are ignored and overwritten by fresh responses.
+pub fn create_fdc_cache(configuration: &Configuration, refresh: bool) -> FdcCache {
+    FdcCache::new(
+        PathBuf::from(&configuration.fdc_cache_directory),
+        Duration::from_secs(configuration.fdc_cache_ttl_hours * 60 * 60),
+        refresh)
+}
+
+/// Creates the retry policies for requests to Tandoor and the FDC as configured.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// ### Returns
+/// Tuple of the policy for Tandoor requests and the policy for FDC requests.
+pub fn create_retry_policies(configuration: &Configuration) -> (Arc<RetryPolicy>, Arc<RetryPolicy>) {
+    let retry_base_delay = Duration::from_millis(configuration.retry_base_delay_milliseconds);
+    let retry_max_delay = Duration::from_millis(configuration.retry_max_delay_milliseconds);
+    let tandoor_retry_policy = RetryPolicy::new("Tandoor", configuration.retry_attempts, retry_base_delay, retry_max_delay);
+    // Rate limiting of the FDC is handled by the FDC client, so it must not be retried by the policy.
+    let fdc_retry_policy = RetryPolicy::new("FDC", configuration.retry_attempts, retry_base_delay, retry_max_delay)
+        .without_status(StatusCode::TOO_MANY_REQUESTS);
+    (Arc::new(tandoor_retry_policy), Arc::new(fdc_retry_policy))
+}
+
+/// Connects to the configured Tandoor instance.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - client: The client used for any http requests
+/// - max_concurrent_writes: How many updates may be sent to Tandoor at the same time.
+/// - retry_policy: The policy used to retry requests that failed due to transient errors.
+pub async fn connect_tandoor(configuration: &Configuration, client: &Client, max_concurrent_writes: usize, retry_policy: Arc<RetryPolicy>) -> Result<TandoorClient, Box<dyn Error>> {
+    let tandoor_client = TandoorClient::connect(
+        client.clone(),
+        &configuration.tandoor_url,
+        &configuration.tandoor_api_key,
+        configuration.tandoor_version.as_deref(),
+        max_concurrent_writes,
+        retry_policy).await?;
+    debug!("The configured Tandoor API endpoint is: {}", tandoor_client.base_url());
+    debug!("The Tandoor API version used is: {}", tandoor_client.version());
+    Ok(tandoor_client)
+}
+
+/// Loads the mapping file if one is configured.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// ### Returns
+/// The mapping, None if no mapping file is configured or an error if the file could not be read.
+pub fn load_food_mapping(configuration: &Configuration) -> Result<Option<FoodMapping>, Box<dyn Error>> {
+    match &configuration.mapping_file {
+        Some(mapping_file) => {
+            debug!("Loading the mapping file {}", mapping_file);
+            Ok(Some(FoodMapping::load(Path::new(mapping_file))?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Writes the given value as JSON to a file.
+/// ### Parameters
+/// - path: The path of the file. An existing file is overwritten.
+/// - value: The value to write.
+pub fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), Box<dyn Error>> {
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
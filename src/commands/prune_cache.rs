@@ -0,0 +1,23 @@
+//! Removes expired entries from the FDC cache.
+use std::process::ExitCode;
+use log::{error, info};
+use crate::commands::create_fdc_cache;
+use crate::models::configuration::Configuration;
+
+/// Runs the prune-cache command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// ### Returns
+/// The exit code of the program.
+pub fn run(configuration: &Configuration) -> ExitCode {
+    match create_fdc_cache(configuration, false).prune() {
+        Ok((removed, kept)) => {
+            info!("Removed {} expired entries from the FDC cache, {} entries are still valid.", removed, kept);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            error!("Error pruning the FDC cache: {:?}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Restores the foods saved in a snapshot.
+use std::path::Path;
+use std::process::ExitCode;
+use log::{error, info, warn};
+use crate::commands::{build_client, connect_tandoor, create_retry_policies};
+use crate::models::configuration::Configuration;
+use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
+use crate::snapshot::read_snapshot;
+
+/// Runs the restore command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - snapshot: The path of the snapshot file.
+/// ### Returns
+/// The exit code of the program.
+pub async fn run(configuration: &Configuration, snapshot: &Path) -> ExitCode {
+    let foods = match read_snapshot(snapshot) {
+        Ok(foods) => foods,
+        Err(e) => {
+            error!("Error reading the snapshot {}: {:?}", snapshot.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (tandoor_retry_policy, _) = create_retry_policies(configuration);
+    let tandoor_client = match connect_tandoor(configuration, &client, configuration.tandoor_concurrency, tandoor_retry_policy).await {
+        Ok(tandoor_client) => tandoor_client,
+        Err(e) => {
+            error!("Error connecting to Tandoor: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    info!("Restoring {} foods from {}", foods.len(), snapshot.display());
+
+    let mut restored_foods = 0;
+    let mut not_restored_foods = 0;
+    for food in foods {
+        match tandoor_client.update_food(&ApiTandoorFood::from(&food), &food.id).await {
+            Ok(_) => {
+                info!("Successfully restored food {}", food.name);
+                restored_foods += 1;
+            }
+            Err(e) => {
+                warn!("Error restoring food {}: {:?}", food.name, e);
+                not_restored_foods += 1;
+            }
+        }
+    }
+
+    info!("\n {} foods successfully restored. \n {} foods were not restored successfully.", restored_foods, not_restored_foods);
+    if not_restored_foods > 0 { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
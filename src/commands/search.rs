@@ -0,0 +1,104 @@
+//! Searches the FDC database for foods to find their FDC ID.
+use std::process::ExitCode;
+use std::sync::Arc;
+use log::error;
+use crate::commands::{build_client, create_fdc_cache, create_retry_policies};
+use crate::fdc_client::FdcClient;
+use crate::models::command_line_arguments::SearchArgs;
+use crate::models::configuration::Configuration;
+use crate::models::usda::usda_search_request::USDASearchRequest;
+use crate::models::usda::usda_search_result::USDASearchResult;
+
+/// Nutrient IDs of the energy in kcal. Foundation foods often only report the energy calculated with Atwater factors.
+const ENERGY_NUTRIENT_IDS: [i32; 3] = [1008, 2047, 2048];
+/// Nutrient ID of protein.
+const PROTEIN_NUTRIENT_ID: i32 = 1003;
+/// Nutrient ID of total fat.
+const FAT_NUTRIENT_ID: i32 = 1004;
+/// Nutrient ID of carbohydrates.
+const CARBOHYDRATES_NUTRIENT_ID: i32 = 1005;
+/// The maximum number of characters of a description shown in the table.
+const MAX_DESCRIPTION_LENGTH: usize = 60;
+
+/// Runs the search command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - args: The options of the search command.
+/// ### Returns
+/// The exit code of the program.
+pub async fn run(configuration: &Configuration, args: &SearchArgs) -> ExitCode {
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (_, fdc_retry_policy) = create_retry_policies(configuration);
+    let fdc_cache = Arc::new(create_fdc_cache(configuration, false));
+    let fdc_client = FdcClient::new(client, configuration.usda_api_key.clone(), fdc_cache, Vec::new(), configuration.fdc_concurrency, fdc_retry_policy);
+
+    let request = USDASearchRequest {
+        query: args.query.to_string(),
+        data_type: (!args.data_types.is_empty()).then(|| args.data_types.iter().map(|d| d.api_name().to_string()).collect()),
+        page_size: args.page_size,
+        page_number: args.page,
+    };
+    match fdc_client.search(&request).await {
+        Ok(response) => {
+            println!("{}", to_table(&response.foods, false));
+            println!("Page {} of {} ({} foods found).", response.current_page, response.total_pages, response.total_hits);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            error!("Error searching the FDC database: {:?}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Renders search results as table readable by humans. Nutrient amounts are per 100 g.
+/// ### Parameters
+/// - results: The foods found by the search.
+/// - numbered: When set every row starts with its number, starting at 1.
+pub fn to_table(results: &[USDASearchResult], numbered: bool) -> String {
+    if results.is_empty() {
+        return "No foods found.".to_string();
+    }
+
+    let descriptions: Vec<String> = results.iter().map(|r| shorten(&r.description)).collect();
+    let description_width = descriptions.iter().map(|d| d.chars().count()).max().unwrap_or_default().max("Description".len());
+    let data_type_width = results.iter().filter_map(|r| r.data_type.as_deref()).map(|d| d.chars().count()).max().unwrap_or_default().max("Data type".len());
+    let brand_width = results.iter().filter_map(|r| r.brand()).map(|b| b.chars().count()).max().unwrap_or_default().max("Brand".len());
+
+    let number_width = if numbered { results.len().to_string().len() + 2 } else { 0 };
+    let mut table = format!("{:>number_width$}{:>8}  {:<description_width$}  {:<data_type_width$}  {:<brand_width$}  {:>6}  {:>7}  {:>7}  {:>7}\n",
+        "", "FDC ID", "Description", "Data type", "Brand", "kcal", "Protein", "Fat", "Carbs");
+    for (index, (result, description)) in results.iter().zip(descriptions).enumerate() {
+        let number = if numbered { format!("{}  ", index + 1) } else { String::new() };
+        table.push_str(&format!("{:>number_width$}{:>8}  {:<description_width$}  {:<data_type_width$}  {:<brand_width$}  {:>6}  {:>7}  {:>7}  {:>7}\n",
+            number,
+            result.fdc_id,
+            description,
+            result.data_type.as_deref().unwrap_or("-"),
+            result.brand().unwrap_or("-"),
+            format_amount(result.nutrient_amount(&ENERGY_NUTRIENT_IDS)),
+            format_amount(result.nutrient_amount(&[PROTEIN_NUTRIENT_ID])),
+            format_amount(result.nutrient_amount(&[FAT_NUTRIENT_ID])),
+            format_amount(result.nutrient_amount(&[CARBOHYDRATES_NUTRIENT_ID]))));
+    }
+    table
+}
+
+/// Shortens a description so the table stays readable.
+fn shorten(description: &str) -> String {
+    if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+        format!("{}...", description.chars().take(MAX_DESCRIPTION_LENGTH - 3).collect::<String>())
+    } else {
+        description.to_string()
+    }
+}
+
+fn format_amount(amount: Option<f32>) -> String {
+    amount.map_or("-".to_string(), |a| format!("{:.1}", a))
+}
@@ -0,0 +1,96 @@
+//! Shows a single food with its properties.
+use std::process::ExitCode;
+use log::error;
+use crate::commands::{build_client, connect_tandoor, create_retry_policies, load_food_mapping};
+use crate::fdc_id::resolve_fdc_id;
+use crate::food_mapping::FoodMapping;
+use crate::models::mapping_precedence::MappingPrecedence;
+use crate::models::configuration::Configuration;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+use crate::models::tandoor::internal_tandoor_property::InternalTandoorProperty;
+
+/// Runs the show command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - food: The ID or the name of the food. Names are compared ignoring the case.
+/// ### Returns
+/// The exit code of the program.
+pub async fn run(configuration: &Configuration, food: &str) -> ExitCode {
+    let food_mapping = match load_food_mapping(configuration) {
+        Ok(food_mapping) => food_mapping,
+        Err(e) => {
+            error!("Error loading the mapping file: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let (tandoor_retry_policy, _) = create_retry_policies(configuration);
+    let tandoor_client = match connect_tandoor(configuration, &client, configuration.tandoor_concurrency, tandoor_retry_policy).await {
+        Ok(tandoor_client) => tandoor_client,
+        Err(e) => {
+            error!("Error connecting to Tandoor: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let properties = match tandoor_client.get_properties().await {
+        Ok(properties) => properties,
+        Err(e) => {
+            error!("Error fetching food properties: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let foods = match tandoor_client.get_foods(None).await {
+        Ok(loaded_foods) => loaded_foods.foods,
+        Err(e) => {
+            error!("Error fetching foods: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let id = food.parse::<i32>().ok();
+    match foods.iter().find(|f| Some(f.id) == id || f.name.eq_ignore_ascii_case(food)) {
+        Some(found) => {
+            println!("{}", to_table(found, &properties, food_mapping.as_ref(), configuration.mapping_precedence));
+            ExitCode::SUCCESS
+        }
+        None => {
+            error!("No food with the ID or name {} was found.", food);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Renders a food with all properties of the Tandoor instance as table readable by humans.
+/// ### Parameters
+/// - food: The food to render.
+/// - properties: All properties of the Tandoor instance. Properties the food does not have are shown without amount.
+/// - food_mapping: The mapping file if one is configured.
+/// - precedence: Whether the mapping file or the fields of the food are used first.
+fn to_table(food: &InternalTandoorFood, properties: &[InternalTandoorProperty], food_mapping: Option<&FoodMapping>, precedence: MappingPrecedence) -> String {
+    let mut table = format!("{} (ID {})\n  FDC ID: {}\n  URL: {}\n",
+        food.name,
+        food.id,
+        resolve_fdc_id(food, food_mapping, precedence).map_or("-".to_string(), |id| id.to_string()),
+        food.url.as_deref().unwrap_or("-"));
+
+    let width = properties.iter().map(|p| p.name.chars().count()).max().unwrap_or_default().max("Property".len());
+    table.push_str(&format!("  {:<width$}  {:>12}  {:>12}\n", "Property", "Amount", "FDC nutrient"));
+    for property in properties {
+        let amount = food.properties
+            .iter()
+            .find(|p| p.property_type.name == property.name)
+            .and_then(|p| p.property_amount);
+        table.push_str(&format!("  {:<width$}  {:>12}  {:>12}\n",
+            property.name,
+            amount.map_or("-".to_string(), |a| a.to_string()),
+            property.fdc_id.map_or("-".to_string(), |id| id.to_string())));
+    }
+    table
+}
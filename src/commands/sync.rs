@@ -0,0 +1,683 @@
+//! Updates the properties of the Tandoor foods with data from the FDC database.
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use log::{debug, error, info, trace, warn};
+use tokio::sync::Semaphore;
+use crate::auto_match::{AutoMatcher, MatchResult};
+use crate::candidate_picker::{CandidatePicker, PickResult};
+use crate::prompt_queue::PromptQueue;
+use crate::commands::{build_client, connect_tandoor, create_fdc_cache, create_retry_policies, load_food_mapping, write_json};
+use crate::fdc_client::FdcClient;
+use crate::fdc_id::resolve_fdc_id;
+use crate::food_filter::FoodFilter;
+use crate::gram_unit::GramUnit;
+use crate::food_portions::{grams_per_unit, PortionImporter};
+use crate::local_mapping::LocalMapping;
+use crate::merge_policies::MergePolicies;
+use crate::models::command_line_arguments::SyncArgs;
+use crate::models::configuration::Configuration;
+use crate::models::food_diff::FoodDiff;
+use crate::models::match_review::MatchReview;
+use crate::models::merge_policy::MergePolicy;
+use crate::models::merge_policy_override::MergePolicyOverride;
+use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+use crate::models::tandoor::internal_tandoor_food_property::InternalTandoorFoodProperty;
+use crate::models::tandoor::internal_tandoor_property::InternalTandoorProperty;
+use crate::models::usda::usda_food::USDAFood;
+use crate::snapshot::SnapshotWriter;
+use crate::units;
+use crate::tandoor_client::TandoorClient;
+
+/// The number of grams the values of the FDC database refer to.
+const FDC_BASE_GRAMS: f32 = 100.0;
+
+/// Runs the sync command.
+/// ### Parameters
+/// - configuration: The configuration of the program.
+/// - args: The options of the sync command.
+/// ### Returns
+/// The exit code of the program.
+pub async fn run(configuration: &Configuration, args: &SyncArgs) -> ExitCode {
+    let mut merge_policy_overrides = args.merge_policies.clone();
+    if args.override_properties {
+        merge_policy_overrides.insert(0, MergePolicyOverride { property: None, policy: MergePolicy::Always });
+    }
+    let merge_policies = Arc::new(MergePolicies::new(configuration.default_merge_policy, &configuration.merge_policies, &merge_policy_overrides));
+    debug!("Interactive mode enabled: {}", args.interactive);
+    debug!("Override mode enabled: {}", args.override_properties);
+    debug!("Dry run enabled: {}", args.dry_run);
+
+    let fdc_cache = Arc::new(create_fdc_cache(configuration, args.refresh_cache));
+    let food_mapping = Arc::new(match load_food_mapping(configuration) {
+        Ok(food_mapping) => food_mapping,
+        Err(e) => {
+            error!("Error loading the mapping file: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    });
+    let mapping_precedence = configuration.mapping_precedence;
+
+    // Create client for api requests.
+    let client = match build_client(configuration) {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Error creating the HTTP client: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let concurrency = args.concurrency.unwrap_or(configuration.concurrency).max(1);
+    let tandoor_concurrency = args.tandoor_concurrency.unwrap_or(configuration.tandoor_concurrency);
+    let fdc_concurrency = args.fdc_concurrency.unwrap_or(configuration.fdc_concurrency);
+    debug!("Processing {} foods at the same time with {} concurrent Tandoor updates and {} concurrent FDC requests.", concurrency, tandoor_concurrency, fdc_concurrency);
+
+    let (tandoor_retry_policy, fdc_retry_policy) = create_retry_policies(configuration);
+
+    let tandoor_client = Arc::new(match connect_tandoor(configuration, &client, tandoor_concurrency, Arc::clone(&tandoor_retry_policy)).await {
+        Ok(tandoor_client) => tandoor_client,
+        Err(e) => {
+            error!("Error connecting to Tandoor: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    });
+
+    // Get Properties
+    let tandoor_properties: Vec<InternalTandoorProperty> = match tandoor_client.get_properties().await {
+        Ok(props) => {
+            info!("Found {} properties.", props.len());
+            trace!("{}", serde_json::to_string(&props).unwrap());
+            props
+        }
+        Err(e) => {
+            error!("Error fetching food properties: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let tandoor_properties_by_fdc_id: Arc<HashMap<i32, InternalTandoorProperty>> = Arc::new(tandoor_properties
+        .iter()
+        .filter_map(|x| x.fdc_id.map(|fdc_id| (fdc_id, x.clone())))
+        .collect());
+
+    let nutrient_ids: Vec<i32> = tandoor_properties.iter().filter_map(|x| x.fdc_id).collect();
+    let fdc_client = Arc::new(FdcClient::new(client, configuration.usda_api_key.clone(), Arc::clone(&fdc_cache), nutrient_ids, fdc_concurrency, Arc::clone(&fdc_retry_policy)));
+    let auto_matcher = args.auto_match.then(|| Arc::new(AutoMatcher::new(
+        Arc::clone(&fdc_client),
+        args.match_threshold.unwrap_or(configuration.auto_match_threshold),
+        configuration.auto_match_data_types.clone())));
+    let prompt_queue = args.interactive.then(|| Arc::new(PromptQueue::new(CandidatePicker::new(Arc::clone(&fdc_client), configuration.auto_match_data_types.clone()))));
+    let local_mapping = Arc::new(match LocalMapping::load(Path::new(&configuration.local_mapping_file)) {
+        Ok(local_mapping) => local_mapping,
+        Err(e) => {
+            error!("Error loading the local mapping {}: {:?}", configuration.local_mapping_file, e);
+            return ExitCode::FAILURE;
+        }
+    });
+
+    // Get Foods
+    let loaded_foods = match tandoor_client.get_foods(args.modified_since).await {
+        Ok(loaded_foods) => loaded_foods,
+        Err(e) => {
+            error!("Error fetching foods: {:?}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    info!("Found {} foods.", loaded_foods.foods.len());
+    for skipped in &loaded_foods.skipped {
+        warn!("Food {} could not be loaded and will not be updated: {}", skipped, skipped.reason);
+    }
+    let returned_food_number = loaded_foods.foods.len() + loaded_foods.skipped.len();
+    let is_count_mismatch = loaded_foods.expected.is_some_and(|expected| usize::try_from(expected).ok() != Some(returned_food_number));
+    if is_count_mismatch {
+        warn!("Tandoor reported {} foods but {} were returned. Foods might have been added or removed while loading.", loaded_foods.expected.unwrap_or_default(), returned_food_number);
+    }
+    if args.strict && (is_count_mismatch || !loaded_foods.skipped.is_empty()) {
+        error!("Not all foods were loaded successfully ({} could not be read). Stopping execution as strict mode is enabled.", loaded_foods.skipped.len());
+        return ExitCode::FAILURE;
+    }
+    let mut tandoor_foods: Vec<InternalTandoorFood> = loaded_foods.foods;
+
+    // Only process the selected foods.
+    let food_filter = FoodFilter::new(args, &configuration.ignored_foods);
+    for unmatched in food_filter.unmatched_selections(&tandoor_foods) {
+        warn!("No food matches the selected food {}.", unmatched);
+    }
+    let number_of_loaded_foods = tandoor_foods.len();
+    tandoor_foods.retain(|food| food_filter.is_selected(food));
+    if tandoor_foods.len() != number_of_loaded_foods {
+        info!("Selected {} of {} foods.", tandoor_foods.len(), number_of_loaded_foods);
+    }
+
+    // Foods without a property base get the one of the FDC database. Imported portions are converted to it as well.
+    let gram_unit = Arc::new(GramUnit::new(Arc::clone(&tandoor_client), args.dry_run));
+    let portion_importer = match args.import_portions {
+        true => match tandoor_client.get_units().await {
+            Ok(tandoor_units) => {
+                debug!("Found {} units.", tandoor_units.len());
+                Some(Arc::new(PortionImporter::new(tandoor_units, &configuration.portion_units, Arc::clone(&gram_unit), args.override_properties, args.dry_run)))
+            }
+            Err(e) => {
+                error!("Error fetching units: {:?}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        false => None,
+    };
+
+    // Update the foods.
+    let updated_foods = Arc::new(AtomicUsize::new(0));
+    let not_updated_foods = Arc::new(AtomicUsize::new(0));
+    let no_fdc_id = Arc::new(AtomicUsize::new(0));
+    let already_fully_updated = Arc::new(AtomicUsize::new(0));
+    let auto_matched = Arc::new(AtomicUsize::new(0));
+    let created_conversions = Arc::new(AtomicUsize::new(0));
+    let updated_conversions = Arc::new(AtomicUsize::new(0));
+    let match_reviews: Arc<Mutex<Vec<MatchReview>>> = Arc::new(Mutex::new(Vec::new()));
+    let number_of_properties =  tandoor_properties.len();
+    let food_diffs: Arc<Mutex<Vec<FoodDiff>>> = Arc::new(Mutex::new(Vec::new()));
+    let snapshot_writer = Arc::new(SnapshotWriter::new(Path::new(&configuration.snapshot_directory)));
+    let workers = Arc::new(Semaphore::new(concurrency));
+    let mut handles = vec![];
+    for mut food in tandoor_foods.into_iter(){
+        // Directly continue if number of properties of food is equal to number of properties
+        // retrieved from Tandoor and no existing value may be overwritten.
+        if !merge_policies.can_overwrite() && food.properties.len() == number_of_properties && food.properties_food_unit.is_some() && !args.import_portions{
+            info!("{} is already fully updated.", food.name);
+            {
+                already_fully_updated.fetch_add(1, Ordering::SeqCst);  // Lock the mutex to modify the shared counter
+            }
+            continue;
+        }
+        
+        let tandoor_properties_by_fdc_id = Arc::clone(&tandoor_properties_by_fdc_id);
+        let updated_foods = Arc::clone(&updated_foods);
+        let not_updated_foods = Arc::clone(&not_updated_foods);
+        let no_fdc_id = Arc::clone(&no_fdc_id);
+        let fdc_client = Arc::clone(&fdc_client);
+        let tandoor_client = Arc::clone(&tandoor_client);
+        let food_diffs = Arc::clone(&food_diffs);
+        let snapshot_writer = Arc::clone(&snapshot_writer);
+        let auto_matcher = auto_matcher.clone();
+        let auto_matched = Arc::clone(&auto_matched);
+        let match_reviews = Arc::clone(&match_reviews);
+        let prompt_queue = prompt_queue.clone();
+        let workers = Arc::clone(&workers);
+        let local_mapping = Arc::clone(&local_mapping);
+        let merge_policies = Arc::clone(&merge_policies);
+        let food_mapping = Arc::clone(&food_mapping);
+        let gram_unit = Arc::clone(&gram_unit);
+        let portion_importer = portion_importer.clone();
+        let created_conversions = Arc::clone(&created_conversions);
+        let updated_conversions = Arc::clone(&updated_conversions);
+        let dry_run = args.dry_run;
+        let food_name = food.name.to_string();
+        
+        // Wait for a free worker before starting the next food.
+        let worker = Arc::clone(&workers).acquire_owned().await.expect("The worker pool was closed.");
+        let handle = tokio::spawn(async move{
+            let mut _worker = worker;
+            debug!("Going to update food {}", food.name);
+            let original_food = food.clone();
+            // Get data from USDA. When no FDC ID was found use the one the user picked in a previous run,
+            // try to match the food automatically and if interactive mode is activated ask the user.
+            let mut found_fdc_id = resolve_fdc_id(&food, food_mapping.as_ref().as_ref(), mapping_precedence);
+            // FDC IDs picked by the user are written to Tandoor right away, so they are kept even if the update fails.
+            let mut is_picked_by_user = false;
+            if found_fdc_id.is_none() {
+                found_fdc_id = local_mapping.fdc_id_for(&food.id);
+                if let Some(id) = found_fdc_id {
+                    debug!("Using FDC ID {} picked in a previous run for food {}.", id, food.name);
+                    is_picked_by_user = true;
+                }
+            }
+            if let (None, Some(auto_matcher)) = (found_fdc_id, &auto_matcher) {
+                found_fdc_id = auto_match_food(auto_matcher, &food, &match_reviews).await;
+                if found_fdc_id.is_some() {
+                    auto_matched.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+            if let (None, Some(prompt_queue)) = (found_fdc_id, &prompt_queue) {
+                // Free the worker while waiting for the user, so the other foods keep syncing.
+                drop(_worker);
+                found_fdc_id = ask_user(prompt_queue, &local_mapping, &food).await;
+                is_picked_by_user = found_fdc_id.is_some();
+                _worker = workers.acquire_owned().await.expect("The worker pool was closed.");
+            }
+            let mut is_snapshot_saved = false;
+            if let (true, Some(id), false) = (is_picked_by_user, found_fdc_id, dry_run) {
+                is_snapshot_saved = link_fdc_id(&tandoor_client, &snapshot_writer, &original_food, &id).await;
+            }
+            let fdc_id: i32;
+            if let Some(id) = found_fdc_id {
+                debug!("Found FDC ID {} for food {}.", id, food.name);
+                fdc_id = id
+            }else{
+                warn!("Food {} does not have a FDC ID and will not be updated.", food.name);
+                {
+                    no_fdc_id.fetch_add(1, Ordering::SeqCst);
+                }
+                return;
+            }
+
+            let (mut usda_food, refused_nutrients) = match get_food_data(&fdc_client, &fdc_id, &tandoor_properties_by_fdc_id).await {
+                Ok(props) => {
+                    debug!("Fetched properties for food {} from the USDA FDC database using {} as the ID", food.name, fdc_id);
+                    // When fetching the data was successful, override FDC ID field with the used one.
+                    food.fdc_id = Some(fdc_id);
+                    props
+                }
+                Err(e) => {
+                    warn!("Error fetching food properties for {} from the FDC database: {:?}", food.name, e);
+                    {
+                        not_updated_foods.fetch_add(1, Ordering::SeqCst);
+                    }
+                    return;
+                }
+            };
+
+            // The FDC values refer to 100 g, so they are rescaled to the amount the values of the food refer to.
+            if food.properties_food_unit.is_none() {
+                let unit = match gram_unit.get().await {
+                    Ok(unit) => unit.clone(),
+                    Err(e) => {
+                        warn!("Error getting the unit {} from Tandoor, food {} will not be updated: {:?}", units::GRAM, food.name, e);
+                        not_updated_foods.fetch_add(1, Ordering::SeqCst);
+                        return;
+                    }
+                };
+                debug!("Setting the properties of food {} to refer to {} {}", food.name, FDC_BASE_GRAMS, units::GRAM);
+                food.properties_food_amount = Some(FDC_BASE_GRAMS);
+                food.properties_food_unit = Some(unit);
+            }
+            let base_grams = match get_base_grams(&tandoor_client, &food).await {
+                Ok(Some(base_grams)) => base_grams,
+                Ok(None) => {
+                    warn!("The properties of food {} refer to {} {} which cannot be converted to grams. Add a unit conversion to grams to the food in Tandoor. The food will not be updated.",
+                        food.name,
+                        food.properties_food_amount.unwrap_or(FDC_BASE_GRAMS),
+                        food.properties_food_unit.as_ref().map_or("", |unit| &unit.name));
+                    not_updated_foods.fetch_add(1, Ordering::SeqCst);
+                    return;
+                }
+                Err(e) => {
+                    warn!("Error getting the unit conversions of food {}: {:?}", food.name, e);
+                    not_updated_foods.fetch_add(1, Ordering::SeqCst);
+                    return;
+                }
+            };
+            if base_grams != FDC_BASE_GRAMS {
+                trace!("Rescaling the FDC values of food {} to {} g", food.name, base_grams);
+                for nutrient in usda_food.food_nutrients.iter_mut() {
+                    nutrient.amount = nutrient.amount.map(|amount| amount * base_grams / FDC_BASE_GRAMS);
+                }
+            }
+
+            // Build updated food
+            let (food_id, updated_food) = match create_updated_food(&food, &usda_food, &refused_nutrients, &merge_policies){
+                Ok(props) => {
+                    debug!("Build updated food for {}", food.name);
+                    props
+                }
+                Err(e) => {
+                    warn!("Error creating updated food for {}: {:?}", food.name, e);
+                    {
+                        not_updated_foods.fetch_add(1, Ordering::SeqCst);
+                    }
+                    return;
+                }
+            };
+
+            // Unit conversions are not part of the food, so they are neither saved to the snapshot nor restored.
+            // They are only imported once the food itself was updated.
+            let import_portions = || async {
+                if let Some(portion_importer) = &portion_importer {
+                    match portion_importer.import(&tandoor_client, &food, &usda_food).await {
+                        Ok(portion_import) => {
+                            created_conversions.fetch_add(portion_import.created, Ordering::SeqCst);
+                            updated_conversions.fetch_add(portion_import.updated, Ordering::SeqCst);
+                        }
+                        Err(e) => warn!("Error importing the portions of food {}: {:?}", food.name, e),
+                    }
+                }
+            };
+
+            let food_diff = FoodDiff::new(&food, &updated_food);
+            if dry_run {
+                println!("{}", food_diff.to_table());
+                food_diffs.lock().unwrap().push(food_diff);
+                updated_foods.fetch_add(1, Ordering::SeqCst);
+                import_portions().await;
+                return;
+            }
+
+            // Save the original food, so the update can be undone.
+            if !is_snapshot_saved {
+                if let Err(e) = snapshot_writer.save(&original_food) {
+                    warn!("Error saving food {} to the snapshot, the food will not be updated: {:?}", food.name, e);
+                    not_updated_foods.fetch_add(1, Ordering::SeqCst);
+                    return;
+                }
+            }
+
+            // Update food in Tandoor database.
+            let is_updated = match tandoor_client.update_food(&updated_food, &food_id).await{
+                Ok(_) => {
+                    {
+                        updated_foods.fetch_add(1, Ordering::SeqCst);
+                        info!("Successfully updated food {}", updated_food.name);
+                    }
+                    food_diffs.lock().unwrap().push(food_diff);
+                    true
+                }
+                Err(e) => {
+                    warn!("Error updating food {}: {:?}", updated_food.name, e);
+                    { 
+                        not_updated_foods.fetch_add(1, Ordering::SeqCst);
+                    }
+                    false
+                }
+            };
+            if is_updated {
+                import_portions().await;
+            }
+        });
+        
+        handles.push((food_name, handle));
+    }
+    
+    // A food task that panicked is counted as not updated, the results of the other foods are still reported.
+    for (food_name, handle) in handles{
+        if let Err(e) = handle.await {
+            error!("Updating food {} failed unexpectedly: {:?}", food_name, e);
+            not_updated_foods.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    
+    if let Some(diff_output) = &args.diff_output {
+        let food_diffs = food_diffs.lock().unwrap();
+        match write_json(diff_output, &*food_diffs) {
+            Ok(_) => info!("Wrote the changes of {} foods to {}", food_diffs.len(), diff_output.display()),
+            Err(e) => error!("Error writing the changes to {}: {:?}", diff_output.display(), e),
+        }
+    }
+
+    if args.auto_match {
+        let match_reviews = match_reviews.lock().unwrap();
+        info!("{} foods were matched automatically, {} foods need a review.", auto_matched.load(Ordering::SeqCst), match_reviews.len());
+        if !match_reviews.is_empty() {
+            let review_file = Path::new(&configuration.auto_match_review_file);
+            match write_json(review_file, &*match_reviews) {
+                Ok(_) => info!("Wrote the candidates of foods that could not be matched to {}", review_file.display()),
+                Err(e) => error!("Error writing the candidates to {}: {:?}", review_file.display(), e),
+            }
+        }
+    }
+
+    if snapshot_writer.path().exists() {
+        info!("The original foods were saved to {}. Run \"restore {}\" to undo the changes.", snapshot_writer.path().display(), snapshot_writer.path().display());
+    }
+    if args.import_portions {
+        info!("{} unit conversions were added and {} were updated from FDC portions.", created_conversions.load(Ordering::SeqCst), updated_conversions.load(Ordering::SeqCst));
+    }
+    if args.dry_run {
+        info!("Dry run: No food was changed in Tandoor. Foods counted as updated would have been updated.");
+    }
+    info!("\n {} foods successfully updated. \n {} foods were not updated successfully. \
+        \n {} foods did not have a FDC ID. \n {} foods were already completely updated. \
+        \n {} Tandoor requests and {} FDC requests were retried.", 
+        updated_foods.load(Ordering::SeqCst), 
+        not_updated_foods.load(Ordering::SeqCst), 
+        no_fdc_id.load(Ordering::SeqCst), 
+        already_fully_updated.load(Ordering::SeqCst),
+        tandoor_retry_policy.retries(),
+        fdc_retry_policy.retries());
+
+    ExitCode::SUCCESS
+}
+/// Matches a food without FDC ID by searching the FDC database for its name.
+/// ### Parameters
+/// - auto_matcher: The matcher searching the FDC database.
+/// - food: The food to match.
+/// - match_reviews: The foods that need a review. The candidates of the food are added if it could not be matched.
+/// ### Returns
+/// The FDC ID of the matched food or None if no candidate was good enough.
+async fn auto_match_food(auto_matcher: &AutoMatcher, food: &InternalTandoorFood, match_reviews: &Mutex<Vec<MatchReview>>) -> Option<i32> {
+    match auto_matcher.find(&food.name).await {
+        Ok(MatchResult::Matched(candidate)) => {
+            info!("Matched food {} to \"{}\" (FDC ID {}, score {:.2}).", food.name, candidate.description, candidate.fdc_id, candidate.score);
+            Some(candidate.fdc_id)
+        }
+        Ok(MatchResult::Unsure(candidates)) => {
+            debug!("No candidate for food {} is good enough to be matched automatically.", food.name);
+            match_reviews.lock().unwrap().push(MatchReview {
+                food_id: food.id,
+                food_name: food.name.to_string(),
+                candidates,
+            });
+            None
+        }
+        Err(e) => {
+            warn!("Error searching the FDC database for food {}: {:?}", food.name, e);
+            None
+        }
+    }
+}
+
+/// Asks the user to pick the FDC food of a food unless the user asked to never be asked about it again.
+/// ### Parameters
+/// - prompt_queue: The queue of foods the user is asked about.
+/// - local_mapping: The decisions made in previous runs. The decision of the user is added.
+/// - food: The food for which the FDC ID should be retrieved.
+/// ### Returns
+/// The FDC ID picked by the user or None if the food was skipped.
+async fn ask_user(prompt_queue: &PromptQueue, local_mapping: &LocalMapping, food: &InternalTandoorFood) -> Option<i32> {
+    if local_mapping.is_skipped(&food.id) {
+        debug!("Not asking for food {} as it was skipped permanently.", food.name);
+        return None;
+    }
+    match prompt_queue.ask(food).await {
+        PickResult::Picked(fdc_id) => {
+            if let Err(e) = local_mapping.remember_fdc_id(food, &fdc_id) {
+                warn!("Error remembering FDC ID {} for food {}: {:?}", fdc_id, food.name, e);
+            }
+            Some(fdc_id)
+        }
+        PickResult::Skipped => None,
+        PickResult::Ignored => {
+            if let Err(e) = local_mapping.remember_skip(food) {
+                warn!("Error remembering to skip food {}: {:?}", food.name, e);
+            }
+            None
+        }
+    }
+}
+
+/// Writes the FDC ID picked by the user to the food in Tandoor.
+/// ### Parameters
+/// - tandoor_client: The client used to update the food.
+/// - snapshot_writer: The snapshot the food is saved to before it is changed.
+/// - food: The food as it currently is in Tandoor.
+/// - fdc_id: The picked FDC ID.
+/// ### Returns
+/// Whether the food was saved to the snapshot.
+async fn link_fdc_id(tandoor_client: &TandoorClient, snapshot_writer: &SnapshotWriter, food: &InternalTandoorFood, fdc_id: &i32) -> bool {
+    if let Err(e) = snapshot_writer.save(food) {
+        warn!("Error saving food {} to the snapshot, the FDC ID will not be written to Tandoor yet: {:?}", food.name, e);
+        return false;
+    }
+    match tandoor_client.update_fdc_id(&food.id, fdc_id).await {
+        Ok(_) => debug!("Wrote FDC ID {} to food {}.", fdc_id, food.name),
+        Err(e) => warn!("Error writing FDC ID {} to food {}: {:?}", fdc_id, food.name, e),
+    }
+    true
+}
+
+/// Gets how many grams of a food the property values of the food refer to.
+/// ### Parameters
+/// - tandoor_client: The client used to interact with Tandoor.
+/// - food: The food.
+/// ### Returns
+/// The grams or None if the unit of the food cannot be converted to grams.
+/// ### Remarks
+/// Mass units are converted directly. For other units (e.g. piece) the unit conversions of the food in Tandoor are used.
+async fn get_base_grams(tandoor_client: &TandoorClient, food: &InternalTandoorFood) -> Result<Option<f32>, Box<dyn Error>> {
+    let amount = food.properties_food_amount.unwrap_or(FDC_BASE_GRAMS);
+    let Some(unit) = &food.properties_food_unit else {
+        return Ok(Some(amount));
+    };
+    if let Some(grams) = units::convert(amount, &unit.name, units::GRAM, 0) {
+        return Ok(Some(grams));
+    }
+
+    let conversions = tandoor_client.get_unit_conversions(&food.id).await?;
+    Ok(conversions
+        .iter()
+        .find_map(|conversion| grams_per_unit(conversion, unit))
+        .map(|grams| amount * grams))
+}
+
+///  Gets the data of the requested food from the USDA database and filters out properties that are not wanted.
+/// ### Parameters
+/// - fdc_client: The client used to retrieve foods from the FDC database
+/// - fdc_id: The FDC ID of the food to search for
+/// - tandoor_properties_by_fdc_id: A HashMap containing the FDC ID of a property and the property in the Tandoor instance
+/// ### Returns
+/// The [USDAFood] holding only the nutrients that are Tandoor properties, in the units of the Tandoor properties,
+/// and the FDC IDs of the nutrients that were left out as their unit cannot be converted, or an error.
+/// ### Remarks
+/// As the Tandoor API requires a property that we want to add to be identified by the name of the property we need to replace the name of FDC food property 
+/// with the name the user set in the Tandoor instance. Thus, we need the property name and not only the property id.
+/// Nutrients that cannot be converted to the unit of their Tandoor property are left out.
+async fn get_food_data(fdc_client: &FdcClient, fdc_id: &i32, tandoor_properties_by_fdc_id: &HashMap<i32, InternalTandoorProperty>) -> Result<(USDAFood, HashSet<i32>), Box<dyn Error>>{
+
+    let mut food = fdc_client.get_food(fdc_id).await?;
+    let mut refused_nutrients = HashSet::new();
+    
+    // Filter the properties out that we do not want
+    food.food_nutrients.retain(|x| tandoor_properties_by_fdc_id.contains_key(&x.nutrient_information.id));
+
+    // Update the names and units of the usda food properties with the names and units of the tandoor properties
+    food.food_nutrients.retain_mut(|nutrient| {
+        let Some(property) = tandoor_properties_by_fdc_id.get(&nutrient.nutrient_information.id) else {
+            return false;
+        };
+        nutrient.nutrient_information.name = property.name.clone();
+        if let (Some(amount), Some(from), Some(to)) = (nutrient.amount, &nutrient.nutrient_information.unit_name, &property.unit) {
+            match units::convert(amount, from, to, nutrient.nutrient_information.id) {
+                Some(converted) => {
+                    trace!("Converted {} {} of {} to {} {}", amount, from, property.name, converted, to);
+                    nutrient.amount = Some(converted);
+                }
+                None => {
+                    warn!("The FDC database has {} of FDC food {} in {} which cannot be converted to {}, the unit of the property in Tandoor. The property is not imported.", property.name, fdc_id, from, to);
+                    refused_nutrients.insert(nutrient.nutrient_information.id);
+                    return false;
+                }
+            }
+        }
+        nutrient.nutrient_information.unit_name = property.unit.clone().or(nutrient.nutrient_information.unit_name.take());
+        true
+    });
+
+    Ok((food, refused_nutrients))
+}
+
+/// Creates the updated food object to send to Tandoor
+/// ### Parameters
+/// - tandoor_food: The current representation of the food as requested from the Tandoor instance.
+/// - usda_food: The food with its nutrients retrieved from the FoodData Central.
+/// - refused_nutrients: The FDC IDs of the nutrients the FDC food has in a unit that cannot be converted.
+/// - merge_policies: Decide per property whether existing values are overwritten.
+/// ### Returns
+/// Tuple representing the id of the food and a food item that can be sent to the Tandoor API in order to update it or an error.
+/// ### Remarks
+/// Missing properties are always added. Properties without an FDC ID in the database and properties whose FDC
+/// nutrient could not be converted are always kept.
+fn create_updated_food(tandoor_food: &InternalTandoorFood, usda_food: &USDAFood, refused_nutrients: &HashSet<i32>, merge_policies: &MergePolicies) -> Result<(i32, ApiTandoorFood), Box<dyn Error>>{
+    let mut local_food = (*tandoor_food).clone();
+
+    // Properties that always follow the FDC database are removed if the FDC food does not have them.
+    local_food.properties.retain(|p| {
+        let is_in_usda_food = usda_food.food_nutrients.iter().any(|n| p.property_type.fdc_id == Option::from(n.nutrient_information.id));
+        let is_refused = p.property_type.fdc_id.is_some_and(|fdc_id| refused_nutrients.contains(&fdc_id));
+        let is_removed = p.property_type.fdc_id.is_some() && !is_in_usda_food && !is_refused && merge_policies.policy_for(&p.property_type.name) == MergePolicy::Always;
+        if is_removed {
+            trace!("Removing property {} from food {} as the FDC food does not have it.", p.property_type.name, tandoor_food.name);
+        }
+        !is_removed
+    });
+
+    for usda_nutrient in usda_food.food_nutrients.iter(){
+        let present_property = local_food.properties.iter_mut().find(|a| {
+            a.property_type.fdc_id == Option::from(usda_nutrient.nutrient_information.id)
+        });
+        match present_property {
+            Some(property) => {
+                let policy = merge_policies.policy_for(&property.property_type.name);
+                if policy.should_overwrite(property.property_amount, usda_nutrient.amount) {
+                    trace!("Overwriting property {} of food {} as the merge policy is {}", property.property_type.name, local_food.name, policy);
+                    property.property_amount = usda_nutrient.amount;
+                }
+            }
+            None => {
+                local_food.properties.push(InternalTandoorFoodProperty::from(usda_nutrient));
+                trace!("Adding property {} to food {}", usda_nutrient.nutrient_information.name, local_food.name)
+            }
+        }
+    }    
+    Ok((tandoor_food.id, ApiTandoorFood::from(local_food)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::usda::usda_nutrient::USDANutrient;
+
+    fn food_property(name: &str, fdc_id: i32, amount: f32) -> InternalTandoorFoodProperty {
+        InternalTandoorFoodProperty {
+            property_amount: Some(amount),
+            property_type: InternalTandoorProperty { name: name.to_string(), fdc_id: Some(fdc_id), unit: None },
+        }
+    }
+
+    fn tandoor_food(properties: Vec<InternalTandoorFoodProperty>) -> InternalTandoorFood {
+        InternalTandoorFood {
+            id: 1,
+            name: "Maple syrup".to_string(),
+            fdc_id: Some(169661),
+            properties,
+            url: None,
+            properties_food_amount: None,
+            properties_food_unit: None,
+        }
+    }
+
+    fn usda_food(nutrients: Vec<USDANutrient>) -> USDAFood {
+        USDAFood { fdc_id: 169661, food_nutrients: nutrients, food_portions: Vec::new() }
+    }
+
+    fn always() -> MergePolicies {
+        MergePolicies::new(MergePolicy::Always, &HashMap::new(), &[])
+    }
+
+    fn amount_of(food: &ApiTandoorFood, name: &str) -> Option<f32> {
+        food.properties.iter().find(|p| p.property_type.name == name).map(|p| p.property_amount)
+    }
+
+    #[test]
+    fn refused_conversions_keep_the_present_value_under_always() {
+        let food = tandoor_food(vec![food_property("Vitamin D", 1110, 2.5)]);
+        let (_, updated) = create_updated_food(&food, &usda_food(Vec::new()), &HashSet::from([1110]), &always()).unwrap();
+        assert_eq!(amount_of(&updated, "Vitamin D"), Some(2.5));
+    }
+
+    #[test]
+    fn missing_nutrients_are_removed_under_always() {
+        let food = tandoor_food(vec![food_property("Vitamin D", 1110, 2.5)]);
+        let (_, updated) = create_updated_food(&food, &usda_food(Vec::new()), &HashSet::new(), &always()).unwrap();
+        assert_eq!(amount_of(&updated, "Vitamin D"), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! On-disk cache for foods retrieved from the FoodData Central.
+//!
+//! Every food is stored as its own file named after its FDC ID, holding the raw response of the FDC database
+//! together with the time it was fetched.
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use log::{debug, trace, warn};
+use crate::models::usda::usda_cache_entry::USDACacheEntry;
+use crate::models::usda::usda_food::USDAFood;
+
+/// Cache for foods retrieved from the FoodData Central.
+pub struct FdcCache {
+    directory: PathBuf,
+    ttl: Duration,
+    refresh: bool,
+}
+
+impl FdcCache {
+    /// Creates a new cache.
+    /// ### Parameters
+    /// - directory: The directory the cache entries are stored in.
+    /// - ttl: How long a cache entry is valid.
+    /// - refresh: When set, cached entries are ignored and overwritten by fresh responses.
+    pub fn new(directory: PathBuf, ttl: Duration, refresh: bool) -> Self {
+        FdcCache { directory, ttl, refresh }
+    }
+
+    /// Gets a food from the cache.
+    /// ### Parameters
+    /// - fdc_id: The FDC ID of the food.
+    /// - nutrient_ids: The IDs of the nutrients that have to be present in the cached response.
+    /// ### Returns
+    /// The cached food or None if it is not cached, expired, was limited to other nutrients or the cache is being refreshed.
+    pub fn get(&self, fdc_id: &i32, nutrient_ids: &[i32]) -> Option<USDAFood> {
+        if self.refresh {
+            return None;
+        }
+
+        let path = self.entry_path(fdc_id);
+        let content = fs::read_to_string(&path).ok()?;
+        let entry: USDACacheEntry = match serde_json::from_str(&content) {
+            Ok(entry) => entry,
+            Err(e) => {
+                debug!("Ignoring unreadable cache entry {}: {:?}", path.display(), e);
+                return None;
+            }
+        };
+
+        if self.is_expired(&entry) {
+            trace!("Cache entry for FDC ID {} is expired.", fdc_id);
+            return None;
+        }
+
+        if let Some(cached_nutrient_ids) = &entry.nutrient_ids {
+            if !nutrient_ids.iter().all(|id| cached_nutrient_ids.contains(id)) {
+                trace!("Cache entry for FDC ID {} does not contain all requested nutrients.", fdc_id);
+                return None;
+            }
+        }
+
+        match serde_json::from_value(entry.food) {
+            Ok(food) => {
+                trace!("Found FDC ID {} in the cache.", fdc_id);
+                Some(food)
+            }
+            Err(e) => {
+                debug!("Ignoring cache entry {} that is not a valid food: {:?}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Stores the raw response of the FDC database for a food.
+    /// Failing to write the cache is not fatal and only logged.
+    /// ### Parameters
+    /// - fdc_id: The FDC ID of the food.
+    /// - food: The raw food as returned by the FDC database.
+    /// - nutrient_ids: The IDs of the nutrients the response was limited to (None if it was not limited).
+    pub fn store(&self, fdc_id: &i32, food: &serde_json::Value, nutrient_ids: Option<&[i32]>) {
+        if let Err(e) = self.try_store(fdc_id, food, nutrient_ids) {
+            warn!("Could not write FDC ID {} to the cache: {:?}", fdc_id, e);
+        }
+    }
+
+    /// Removes all expired or unreadable entries from the cache.
+    /// ### Returns
+    /// Tuple of the number of removed and kept entries or an error if the cache directory could not be read.
+    pub fn prune(&self) -> Result<(usize, usize), Box<dyn Error>> {
+        let mut removed = 0;
+        let mut kept = 0;
+        if !self.directory.exists() {
+            return Ok((removed, kept));
+        }
+
+        for dir_entry in fs::read_dir(&self.directory)? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let is_valid = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<USDACacheEntry>(&content).ok())
+                .is_some_and(|entry| !self.is_expired(&entry));
+            if is_valid {
+                kept += 1;
+            } else {
+                trace!("Removing cache entry {}", path.display());
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok((removed, kept))
+    }
+
+    fn try_store(&self, fdc_id: &i32, food: &serde_json::Value, nutrient_ids: Option<&[i32]>) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(&self.directory)?;
+        let entry = USDACacheEntry {
+            fetched_at: now(),
+            nutrient_ids: nutrient_ids.map(|ids| ids.to_vec()),
+            food: food.clone(),
+        };
+        fs::write(self.entry_path(fdc_id), serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+
+    fn is_expired(&self, entry: &USDACacheEntry) -> bool {
+        now().saturating_sub(entry.fetched_at) > self.ttl.as_secs()
+    }
+
+    fn entry_path(&self, fdc_id: &i32) -> PathBuf {
+        self.directory.join(format!("{}.json", fdc_id))
+    }
+}
+
+/// The current time as unix timestamp in seconds.
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
@@ -0,0 +1,269 @@
+//! Client used to retrieve foods from the FoodData Central.
+//!
+//! Foods are requested one by one by the update tasks but fetched from the FDC in batches using the multi-food
+//! endpoint. Requests arriving within a short window are grouped, sent together and the results are handed back
+//! to the requesting tasks. All requests share one [RateLimiter] so the rate limit of the API key is respected.
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+use log::{debug, trace, warn};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use tokio::sync::{mpsc, oneshot, Semaphore};
+use tokio::time::Instant;
+use crate::fdc_cache::FdcCache;
+use crate::fdc_nutrients;
+use crate::rate_limiter::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::models::usda::usda_food::USDAFood;
+use crate::models::usda::usda_foods_request::USDAFoodsRequest;
+use crate::models::usda::usda_search_request::USDASearchRequest;
+use crate::models::usda::usda_search_response::USDASearchResponse;
+
+/// The endpoint of the FDC returning multiple foods at once.
+const FOODS_ENDPOINT: &str = "https://api.nal.usda.gov/fdc/v1/foods";
+/// The endpoint of the FDC searching foods.
+const SEARCH_ENDPOINT: &str = "https://api.nal.usda.gov/fdc/v1/foods/search";
+/// The endpoint of the FDC listing foods page by page.
+const FOODS_LIST_ENDPOINT: &str = "https://api.nal.usda.gov/fdc/v1/foods/list";
+/// The maximum number of foods the FDC returns per request.
+const MAX_BATCH_SIZE: usize = 20;
+/// How long to wait for further requests before a batch is sent.
+const BATCH_WINDOW: Duration = Duration::from_millis(100);
+
+/// How often a request is sent again after being rate-limited.
+const MAX_RATE_LIMITED_ATTEMPTS: u32 = 10;
+/// The number of requests per hour allowed by a regular FDC API key.
+const DEFAULT_REQUESTS_PER_HOUR: u32 = 1000;
+
+/// Result of a single food request handed back to the requesting task.
+type FoodResult = Result<USDAFood, String>;
+
+/// A food requested by an update task.
+struct FoodRequest {
+    fdc_id: i32,
+    respond_to: oneshot::Sender<FoodResult>,
+}
+
+/// Client for the FDC database.
+pub struct FdcClient {
+    cache: Arc<FdcCache>,
+    nutrient_ids: Vec<i32>,
+    sender: mpsc::UnboundedSender<FoodRequest>,
+    batcher: Arc<Batcher>,
+}
+
+impl FdcClient {
+    /// Creates a new client and starts the task sending the batched requests.
+    /// ### Parameters
+    /// - client: The client used for any http requests
+    /// - usda_api_key: The API key for the FoodData Central database
+    /// - cache: The cache that is consulted before and updated after calling the FDC database
+    /// - nutrient_ids: The IDs of the nutrients that are mapped to Tandoor properties.
+    /// - max_concurrent_requests: How many requests may be sent to the FDC at the same time.
+    /// - retry_policy: The policy used to retry requests that failed due to transient errors.
+    ///   Rate-limited requests are handled by the client itself and not retried by the policy.
+    pub fn new(client: Client, usda_api_key: String, cache: Arc<FdcCache>, nutrient_ids: Vec<i32>, max_concurrent_requests: usize, retry_policy: Arc<RetryPolicy>) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let nutrient_numbers = fdc_nutrients::numbers_for(&nutrient_ids);
+        if nutrient_numbers.is_none() {
+            debug!("Not all nutrients can be filtered by the FDC. All nutrients will be requested.");
+        }
+
+        let batcher = Arc::new(Batcher {
+            client,
+            usda_api_key,
+            cache: Arc::clone(&cache),
+            rate_limiter: Arc::new(RateLimiter::new(DEFAULT_REQUESTS_PER_HOUR)),
+            retry_policy,
+            nutrient_ids: nutrient_ids.clone(),
+            nutrient_numbers,
+        });
+        tokio::spawn(Arc::clone(&batcher).run(receiver, max_concurrent_requests));
+
+        FdcClient { cache, nutrient_ids, sender, batcher }
+    }
+
+    /// Gets a food from the cache or the FDC database.
+    /// ### Parameters
+    /// - fdc_id: The FDC ID of the food.
+    /// ### Returns
+    /// The food or an error if it could not be retrieved.
+    pub async fn get_food(&self, fdc_id: &i32) -> Result<USDAFood, Box<dyn Error>> {
+        if let Some(food) = self.cache.get(fdc_id, &self.nutrient_ids) {
+            debug!("Using cached FDC data for FDC ID {}", fdc_id);
+            return Ok(food);
+        }
+
+        let (respond_to, response) = oneshot::channel();
+        self.sender
+            .send(FoodRequest { fdc_id: *fdc_id, respond_to })
+            .map_err(|_| "The FDC request queue is closed.")?;
+        match response.await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err("The FDC request was dropped without an answer.".into()),
+        }
+    }
+
+    /// Searches the FDC database for foods.
+    /// ### Parameters
+    /// - request: The search terms and the page to return.
+    /// ### Returns
+    /// The requested page of matching foods or an error if the search failed.
+    /// ### Remarks
+    /// Search results are not cached as they change whenever foods are added to the FDC database.
+    pub async fn search(&self, request: &USDASearchRequest) -> Result<USDASearchResponse, Box<dyn Error>> {
+        trace!("Searching the FDC by calling {} with {}", SEARCH_ENDPOINT, serde_json::to_string(request)?);
+        let response = self.batcher
+            .send_rate_limited(|| self.batcher.client.post(SEARCH_ENDPOINT).json(request))
+            .await?;
+        Ok(response.json().await?)
+    }
+}
+
+/// Checks whether the FDC accepts the API key by requesting a single food.
+/// ### Parameters
+/// - client: The client used for any http requests
+/// - usda_api_key: The API key for the FoodData Central database
+/// ### Returns
+/// An error if the FDC cannot be reached or rejects the API key.
+pub async fn check_api_key(client: &Client, usda_api_key: &str) -> Result<(), Box<dyn Error>> {
+    let response = client
+        .get(format!("{}?pageSize=1", FOODS_LIST_ENDPOINT))
+        .header("X-Api-Key", usda_api_key)
+        .send()
+        .await?;
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => Err("The FDC rejected the API key.".into()),
+        status => Err(format!("The FDC responded with status {}.", status).into()),
+    }
+}
+
+/// Groups food requests into batches and sends them to the FDC.
+struct Batcher {
+    client: Client,
+    usda_api_key: String,
+    cache: Arc<FdcCache>,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: Arc<RetryPolicy>,
+    nutrient_ids: Vec<i32>,
+    nutrient_numbers: Option<Vec<u32>>,
+}
+
+impl Batcher {
+    /// Collects requests into batches until all [FdcClient]s are dropped.
+    /// ### Parameters
+    /// - receiver: The queue of requested foods.
+    /// - max_concurrent_requests: How many batches may be requested from the FDC at the same time.
+    async fn run(self: Arc<Self>, mut receiver: mpsc::UnboundedReceiver<FoodRequest>, max_concurrent_requests: usize) {
+        let request_slots = Arc::new(Semaphore::new(max_concurrent_requests.max(1)));
+        loop {
+            // Wait for a free slot first, so requests keep piling up into the next batch while all slots are busy.
+            let Ok(request_slot) = Arc::clone(&request_slots).acquire_owned().await else {
+                return;
+            };
+            let Some(first) = receiver.recv().await else {
+                return;
+            };
+            let mut pending: HashMap<i32, Vec<oneshot::Sender<FoodResult>>> = HashMap::new();
+            pending.entry(first.fdc_id).or_default().push(first.respond_to);
+
+            let deadline = Instant::now() + BATCH_WINDOW;
+            while pending.len() < MAX_BATCH_SIZE {
+                match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                    Ok(Some(request)) => pending.entry(request.fdc_id).or_default().push(request.respond_to),
+                    _ => break,
+                }
+            }
+
+            let batcher = Arc::clone(&self);
+            tokio::spawn(async move {
+                batcher.fetch_batch(pending).await;
+                drop(request_slot);
+            });
+        }
+    }
+
+    /// Fetches a batch of foods and hands the results back to the requesting tasks.
+    async fn fetch_batch(&self, mut pending: HashMap<i32, Vec<oneshot::Sender<FoodResult>>>) {
+        let fdc_ids: Vec<i32> = pending.keys().copied().collect();
+        debug!("Requesting {} foods from the FDC database.", fdc_ids.len());
+
+        let foods = match self.request_foods(&fdc_ids).await {
+            Ok(response) => response,
+            Err(e) => {
+                for respond_to in pending.into_values().flatten() {
+                    let _ = respond_to.send(Err(e.clone()));
+                }
+                return;
+            }
+        };
+
+        for raw_food in foods {
+            let food: USDAFood = match serde_json::from_value(raw_food.clone()) {
+                Ok(food) => food,
+                Err(e) => {
+                    warn!("Could not read food returned by the FDC database: {:?}", e);
+                    continue;
+                }
+            };
+            self.cache.store(&food.fdc_id, &raw_food, self.nutrient_numbers.as_ref().map(|_| self.nutrient_ids.as_slice()));
+            for respond_to in pending.remove(&food.fdc_id).unwrap_or_default() {
+                let _ = respond_to.send(Ok(food.clone()));
+            }
+        }
+
+        for (fdc_id, senders) in pending {
+            for respond_to in senders {
+                let _ = respond_to.send(Err(format!("The FDC database did not return a food with FDC ID {}.", fdc_id)));
+            }
+        }
+    }
+
+    /// Requests multiple foods from the FDC database.
+    /// ### Parameters
+    /// - fdc_ids: The FDC IDs of the foods.
+    /// ### Returns
+    /// The raw foods or an error message.
+    async fn request_foods(&self, fdc_ids: &[i32]) -> Result<Vec<serde_json::Value>, String> {
+        let request = USDAFoodsRequest {
+            fdc_ids: fdc_ids.to_vec(),
+            format: "full".to_string(),
+            nutrients: self.nutrient_numbers.clone(),
+        };
+
+        trace!("Getting data from FDC by calling {} with {}", FOODS_ENDPOINT, serde_json::to_string(&request).unwrap_or_default());
+        let response = self.send_rate_limited(|| self.client.post(FOODS_ENDPOINT).json(&request)).await?;
+        response.json().await.map_err(|e| format!("{:?}", e))
+    }
+
+    /// Sends a request to the FDC database respecting the rate limit of the API key.
+    /// ### Parameters
+    /// - build_request: Builds the request without the API key. Called again for every attempt.
+    /// ### Returns
+    /// The successful response or an error message.
+    /// ### Remarks
+    /// When the FDC rate-limits the request all FDC requests are paused and the request is sent again afterwards.
+    async fn send_rate_limited<F: Fn() -> RequestBuilder>(&self, build_request: F) -> Result<Response, String> {
+        for attempt in 1..=MAX_RATE_LIMITED_ATTEMPTS {
+            self.rate_limiter.acquire().await;
+            let http_request = build_request().header("X-Api-Key", &self.usda_api_key);
+            // The FDC only reads data, even for POST requests, so all requests may be retried.
+            let response = self.retry_policy.send_idempotent(http_request)
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+
+            self.rate_limiter.update_from_headers(response.headers());
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                self.rate_limiter.pause(response.headers());
+                debug!("FDC request was rate-limited (attempt {} of {}).", attempt, MAX_RATE_LIMITED_ATTEMPTS);
+                continue;
+            }
+
+            return response.error_for_status().map_err(|e| format!("{:?}", e));
+        }
+
+        Err(format!("The FDC database still rate-limited the request after {} attempts.", MAX_RATE_LIMITED_ATTEMPTS))
+    }
+}
@@ -0,0 +1,56 @@
+//! Finds the FDC ID of a Tandoor food.
+use log::trace;
+use regex::Regex;
+use crate::food_mapping::FoodMapping;
+use crate::models::mapping_precedence::MappingPrecedence;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+
+/// Gets the FDC ID from a given food (either from the URL field or the FDC ID field).
+/// ### Parameters
+/// - food: The food for which the FDC ID should be retrieved.
+/// ### Returns
+/// Option<i32> containing the FDC ID or None if no FDC ID was found in the URL or FDC ID field.
+pub fn get_fdc_id(food: &InternalTandoorFood) -> Option<i32>{
+    // If URL is set use that to get FDC ID
+    // if no URL is set or no FDC ID can be matched from the URL, use FDC ID field.
+    if let Some(fdc_id) = food.url.as_deref().and_then(parse_fdc_url) {
+        trace!("Found FDC ID {} in the URL field.", fdc_id);
+        Some(fdc_id)
+    } else if let Some(fdc_id_of_food) = food.fdc_id {
+        trace!("Found FDC ID {} in FDC ID field.", fdc_id_of_food);
+        Some(fdc_id_of_food)
+    } else {
+        None
+    }
+}
+
+/// Gets the FDC ID of a food from the food itself or from the mapping file.
+/// ### Parameters
+/// - food: The food for which the FDC ID should be retrieved.
+/// - food_mapping: The mapping file if one is configured.
+/// - precedence: Whether the mapping file or the fields of the food are used first.
+/// ### Returns
+/// Option<i32> containing the FDC ID or None if neither source has an FDC ID for the food.
+pub fn resolve_fdc_id(food: &InternalTandoorFood, food_mapping: Option<&FoodMapping>, precedence: MappingPrecedence) -> Option<i32>{
+    let from_file = || {
+        let fdc_id = food_mapping.and_then(|m| m.fdc_id_for(food));
+        if let Some(fdc_id) = fdc_id {
+            trace!("Found FDC ID {} in the mapping file.", fdc_id);
+        }
+        fdc_id
+    };
+    match precedence {
+        MappingPrecedence::Tandoor => get_fdc_id(food).or_else(from_file),
+        MappingPrecedence::File => from_file().or_else(|| get_fdc_id(food)),
+    }
+}
+
+/// Gets the FDC ID from the URL of a food on the FDC website.
+/// ### Parameters
+/// - url: The URL (e.g. https://fdc.nal.usda.gov/fdc-app.html#/food-details/169661/nutrients).
+/// ### Returns
+/// The FDC ID or None if the URL does not point to a food.
+pub fn parse_fdc_url(url: &str) -> Option<i32> {
+    let re = Regex::new(r"food-details/(\d+)").unwrap();
+    re.captures(url).and_then(|caps| caps[1].parse().ok())
+}
@@ -0,0 +1,86 @@
+//! Well-known nutrients of the FoodData Central.
+//!
+//! The FDC identifies nutrients by an ID (used by Tandoor properties) and by a nutrient number (used by some FDC
+//! endpoints to filter the returned nutrients). This table maps between both.
+
+/// A nutrient as defined by the FoodData Central.
+pub struct FdcNutrient {
+    /// The ID of the nutrient.
+    pub id: i32,
+    /// The nutrient number of the nutrient.
+    pub number: &'static str,
+}
+
+/// All nutrients known to the importer.
+pub const NUTRIENTS: &[FdcNutrient] = &[
+    FdcNutrient { id: 1003, number: "203" }, // Protein
+    FdcNutrient { id: 1004, number: "204" }, // Total lipid (fat)
+    FdcNutrient { id: 1005, number: "205" }, // Carbohydrate, by difference
+    FdcNutrient { id: 1008, number: "208" }, // Energy (kcal)
+    FdcNutrient { id: 1062, number: "268" }, // Energy (kJ)
+    FdcNutrient { id: 2047, number: "957" }, // Energy (Atwater General Factors)
+    FdcNutrient { id: 2048, number: "958" }, // Energy (Atwater Specific Factors)
+    FdcNutrient { id: 1009, number: "209" }, // Starch
+    FdcNutrient { id: 1018, number: "221" }, // Alcohol, ethyl
+    FdcNutrient { id: 1051, number: "255" }, // Water
+    FdcNutrient { id: 1057, number: "262" }, // Caffeine
+    FdcNutrient { id: 1079, number: "291" }, // Fiber, total dietary
+    FdcNutrient { id: 2000, number: "269" }, // Sugars, total including NLEA
+    FdcNutrient { id: 1063, number: "269.3" }, // Sugars, Total
+    FdcNutrient { id: 1235, number: "539" }, // Sugars, added
+    FdcNutrient { id: 1253, number: "601" }, // Cholesterol
+    FdcNutrient { id: 1257, number: "605" }, // Fatty acids, total trans
+    FdcNutrient { id: 1258, number: "606" }, // Fatty acids, total saturated
+    FdcNutrient { id: 1292, number: "645" }, // Fatty acids, total monounsaturated
+    FdcNutrient { id: 1293, number: "646" }, // Fatty acids, total polyunsaturated
+    FdcNutrient { id: 1087, number: "301" }, // Calcium, Ca
+    FdcNutrient { id: 1089, number: "303" }, // Iron, Fe
+    FdcNutrient { id: 1090, number: "304" }, // Magnesium, Mg
+    FdcNutrient { id: 1091, number: "305" }, // Phosphorus, P
+    FdcNutrient { id: 1092, number: "306" }, // Potassium, K
+    FdcNutrient { id: 1093, number: "307" }, // Sodium, Na
+    FdcNutrient { id: 1095, number: "309" }, // Zinc, Zn
+    FdcNutrient { id: 1098, number: "312" }, // Copper, Cu
+    FdcNutrient { id: 1099, number: "313" }, // Fluoride, F
+    FdcNutrient { id: 1100, number: "314" }, // Iodine, I
+    FdcNutrient { id: 1101, number: "315" }, // Manganese, Mn
+    FdcNutrient { id: 1103, number: "317" }, // Selenium, Se
+    FdcNutrient { id: 1104, number: "318" }, // Vitamin A, IU
+    FdcNutrient { id: 1106, number: "320" }, // Vitamin A, RAE
+    FdcNutrient { id: 1109, number: "323" }, // Vitamin E (alpha-tocopherol)
+    FdcNutrient { id: 1110, number: "324" }, // Vitamin D (D2 + D3), International Units
+    FdcNutrient { id: 1114, number: "328" }, // Vitamin D (D2 + D3)
+    FdcNutrient { id: 1162, number: "401" }, // Vitamin C, total ascorbic acid
+    FdcNutrient { id: 1165, number: "404" }, // Thiamin
+    FdcNutrient { id: 1166, number: "405" }, // Riboflavin
+    FdcNutrient { id: 1167, number: "406" }, // Niacin
+    FdcNutrient { id: 1170, number: "410" }, // Pantothenic acid
+    FdcNutrient { id: 1175, number: "415" }, // Vitamin B-6
+    FdcNutrient { id: 1176, number: "416" }, // Biotin
+    FdcNutrient { id: 1177, number: "417" }, // Folate, total
+    FdcNutrient { id: 1178, number: "418" }, // Vitamin B-12
+    FdcNutrient { id: 1180, number: "421" }, // Choline, total
+    FdcNutrient { id: 1185, number: "430" }, // Vitamin K (phylloquinone)
+    FdcNutrient { id: 1190, number: "435" }, // Folate, DFE
+];
+
+/// Finds a nutrient by its ID.
+pub fn find(id: i32) -> Option<&'static FdcNutrient> {
+    NUTRIENTS.iter().find(|n| n.id == id)
+}
+
+/// Gets the nutrient numbers to filter FDC responses by.
+/// ### Parameters
+/// - nutrient_ids: The IDs of all nutrients that are needed.
+/// ### Returns
+/// The nutrient numbers or None if not all of them are known or the FDC cannot filter by them.
+pub fn numbers_for(nutrient_ids: &[i32]) -> Option<Vec<u32>> {
+    // The FDC only accepts up to 25 nutrient numbers.
+    if nutrient_ids.is_empty() || nutrient_ids.len() > 25 {
+        return None;
+    }
+    nutrient_ids
+        .iter()
+        .map(|id| find(*id).and_then(|n| n.number.parse().ok()))
+        .collect()
+}
@@ -0,0 +1,133 @@
+//! Selects the foods a sync processes.
+use regex::Regex;
+use crate::models::command_line_arguments::SyncArgs;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+
+/// Decides which foods are processed based on the selection options and the ignore list.
+pub struct FoodFilter {
+    ids: Vec<i32>,
+    names: Vec<String>,
+    name_patterns: Vec<Regex>,
+    exclude_patterns: Vec<Regex>,
+    ignored_foods: Vec<String>,
+}
+
+impl FoodFilter {
+    /// Creates the filter.
+    /// ### Parameters
+    /// - args: The options of the sync command holding the selection and the exclude patterns.
+    /// - ignored_foods: Names or IDs of foods that are never processed.
+    pub fn new(args: &SyncArgs, ignored_foods: &[String]) -> Self {
+        FoodFilter {
+            ids: args.food_ids.clone(),
+            names: args.food_names.clone(),
+            name_patterns: args.name_patterns.clone(),
+            exclude_patterns: args.exclude_patterns.clone(),
+            ignored_foods: ignored_foods.to_vec(),
+        }
+    }
+
+    /// Checks whether a food is processed.
+    /// ### Parameters
+    /// - food: The food to check.
+    /// ### Returns
+    /// True if the food matches any of the selection options (or none are given) and is neither excluded nor ignored.
+    pub fn is_selected(&self, food: &InternalTandoorFood) -> bool {
+        self.is_included(food) && !self.is_excluded(food)
+    }
+
+    /// Gets the selected IDs and names no food matches.
+    /// ### Parameters
+    /// - foods: All loaded foods.
+    pub fn unmatched_selections(&self, foods: &[InternalTandoorFood]) -> Vec<String> {
+        let unmatched_ids = self.ids
+            .iter()
+            .filter(|id| !foods.iter().any(|food| food.id == **id))
+            .map(|id| format!("ID {}", id));
+        let unmatched_names = self.names
+            .iter()
+            .filter(|name| !foods.iter().any(|food| &food.name == *name))
+            .cloned();
+        unmatched_ids.chain(unmatched_names).collect()
+    }
+
+    fn is_included(&self, food: &InternalTandoorFood) -> bool {
+        let has_selection = !self.ids.is_empty() || !self.names.is_empty() || !self.name_patterns.is_empty();
+        !has_selection
+            || self.ids.contains(&food.id)
+            || self.names.contains(&food.name)
+            || self.name_patterns.iter().any(|pattern| pattern.is_match(&food.name))
+    }
+
+    fn is_excluded(&self, food: &InternalTandoorFood) -> bool {
+        self.exclude_patterns.iter().any(|pattern| pattern.is_match(&food.name))
+            || self.ignored_foods.iter().any(|ignored| *ignored == food.name || *ignored == food.id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn food(id: i32, name: &str) -> InternalTandoorFood {
+        InternalTandoorFood {
+            id,
+            name: name.to_string(),
+            fdc_id: None,
+            properties: Vec::new(),
+            url: None,
+            properties_food_amount: None,
+            properties_food_unit: None,
+        }
+    }
+
+    fn filter() -> FoodFilter {
+        FoodFilter {
+            ids: Vec::new(),
+            names: Vec::new(),
+            name_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            ignored_foods: Vec::new(),
+        }
+    }
+
+    fn regex(pattern: &str) -> Regex {
+        Regex::new(pattern).unwrap()
+    }
+
+    #[test]
+    fn all_foods_are_selected_without_selection() {
+        assert!(filter().is_selected(&food(1, "Butter")));
+    }
+
+    #[test]
+    fn foods_are_selected_by_id_name_or_pattern() {
+        let filter = FoodFilter { ids: vec![1], names: vec!["Maple syrup".to_string()], name_patterns: vec![regex("^Cheese")], ..filter() };
+        assert!(filter.is_selected(&food(1, "Butter")));
+        assert!(filter.is_selected(&food(2, "Maple syrup")));
+        assert!(filter.is_selected(&food(3, "Cheese, cheddar")));
+        assert!(!filter.is_selected(&food(4, "Cream cheese")));
+        assert!(!filter.is_selected(&food(5, "maple syrup")));
+    }
+
+    #[test]
+    fn excluded_and_ignored_foods_are_never_selected() {
+        let filter = FoodFilter {
+            ids: vec![1, 2, 3],
+            exclude_patterns: vec![regex("(?i)vegan")],
+            ignored_foods: vec!["Butter".to_string(), "3".to_string()],
+            ..filter()
+        };
+        assert!(!filter.is_selected(&food(1, "Vegan cheese")));
+        assert!(!filter.is_selected(&food(2, "Butter")));
+        assert!(!filter.is_selected(&food(3, "Milk")));
+        assert!(filter.is_selected(&food(2, "Cheese")));
+    }
+
+    #[test]
+    fn unmatched_ids_and_names_are_reported() {
+        let filter = FoodFilter { ids: vec![1, 7], names: vec!["Butter".to_string(), "Ghee".to_string()], ..filter() };
+        let foods = [food(1, "Butter")];
+        assert_eq!(filter.unmatched_selections(&foods), vec!["ID 7".to_string(), "Ghee".to_string()]);
+    }
+}
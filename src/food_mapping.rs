@@ -0,0 +1,177 @@
+//! Mapping files linking Tandoor foods to FDC foods.
+//!
+//! A mapping file holds one [FoodMappingEntry] per food and is meant to be kept under version control. It can be
+//! written as CSV, YAML or JSON; the format is chosen by the extension of the file.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use log::warn;
+use crate::models::food_mapping_entry::FoodMappingEntry;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+
+/// The formats a mapping file can be written in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MappingFormat {
+    Csv,
+    Yaml,
+    Json,
+}
+
+impl MappingFormat {
+    /// Gets the format from the extension of the file.
+    fn from_path(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_lowercase).as_deref() {
+            Some("csv") => Ok(MappingFormat::Csv),
+            Some("yaml") | Some("yml") => Ok(MappingFormat::Yaml),
+            Some("json") => Ok(MappingFormat::Json),
+            _ => Err(format!("The format of the mapping file {} is not supported. Use .csv, .yaml, .yml or .json.", path.display()).into()),
+        }
+    }
+}
+
+/// FDC IDs of foods read from a mapping file.
+pub struct FoodMapping {
+    by_id: HashMap<i32, i32>,
+    by_name: HashMap<String, i32>,
+}
+
+impl FoodMapping {
+    /// Loads a mapping file.
+    /// ### Parameters
+    /// - path: The path of the mapping file.
+    /// ### Returns
+    /// The mapping or an error if the file could not be read.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut mapping = FoodMapping {
+            by_id: HashMap::new(),
+            by_name: HashMap::new(),
+        };
+        for entry in read_entries(path)? {
+            match (entry.food_id, entry.food_name) {
+                (Some(food_id), _) => { mapping.by_id.insert(food_id, entry.fdc_id); }
+                (None, Some(food_name)) => { mapping.by_name.insert(food_name, entry.fdc_id); }
+                (None, None) => warn!("The mapping of FDC ID {} in {} has neither a food ID nor a food name and is ignored.", entry.fdc_id, path.display()),
+            }
+        }
+        Ok(mapping)
+    }
+
+    /// Gets the FDC ID of a food, looking it up by its id first and by its name second.
+    /// ### Parameters
+    /// - food: The food.
+    pub fn fdc_id_for(&self, food: &InternalTandoorFood) -> Option<i32> {
+        self.by_id.get(&food.id).or_else(|| self.by_name.get(&food.name)).copied()
+    }
+}
+
+/// Reads all entries of a mapping file.
+fn read_entries(path: &Path) -> Result<Vec<FoodMappingEntry>, Box<dyn Error>> {
+    match MappingFormat::from_path(path)? {
+        MappingFormat::Csv => csv::Reader::from_path(path)?
+            .deserialize()
+            .map(|entry| entry.map_err(|e| e.into()))
+            .collect(),
+        MappingFormat::Yaml => Ok(serde_yaml::from_str(&fs::read_to_string(path)?)?),
+        MappingFormat::Json => Ok(serde_json::from_str(&fs::read_to_string(path)?)?),
+    }
+}
+
+/// Writes entries to a mapping file. An existing file is overwritten.
+/// ### Parameters
+/// - path: The path of the mapping file. The extension selects the format.
+/// - entries: The entries to write.
+pub fn write_entries(path: &Path, entries: &[FoodMappingEntry]) -> Result<(), Box<dyn Error>> {
+    match MappingFormat::from_path(path)? {
+        MappingFormat::Csv => {
+            let mut writer = csv::Writer::from_path(path)?;
+            for entry in entries {
+                writer.serialize(entry)?;
+            }
+            writer.flush()?;
+        }
+        MappingFormat::Yaml => fs::write(path, serde_yaml::to_string(entries)?)?,
+        MappingFormat::Json => fs::write(path, serde_json::to_string_pretty(entries)?)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use crate::fdc_id::resolve_fdc_id;
+    use crate::models::mapping_precedence::MappingPrecedence;
+
+    /// Writes a mapping file to the temporary directory.
+    fn mapping_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("tandoor_importer-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn food(id: i32, name: &str, fdc_id: Option<i32>) -> InternalTandoorFood {
+        InternalTandoorFood {
+            id,
+            name: name.to_string(),
+            fdc_id,
+            properties: Vec::new(),
+            url: None,
+            properties_food_amount: None,
+            properties_food_unit: None,
+        }
+    }
+
+    #[test]
+    fn reads_csv_with_empty_food_ids_and_names() {
+        let path = mapping_file("empty-fields.csv", "foodId,foodName,fdcId\n42,,169661\n,Butter,173410\n");
+        let mapping = FoodMapping::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mapping.fdc_id_for(&food(42, "Maple syrup", None)), Some(169661));
+        assert_eq!(mapping.fdc_id_for(&food(7, "Butter", None)), Some(173410));
+    }
+
+    #[test]
+    fn rejects_csv_with_empty_fdc_ids() {
+        let path = mapping_file("empty-fdc-id.csv", "foodId,foodName,fdcId\n42,Maple syrup,\n");
+        let mapping = FoodMapping::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(mapping.is_err());
+    }
+
+    #[test]
+    fn reads_yaml() {
+        let path = mapping_file("mapping.yaml", "- foodId: 42\n  fdcId: 169661\n- foodName: Butter\n  fdcId: 173410\n");
+        let mapping = FoodMapping::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(mapping.fdc_id_for(&food(42, "Maple syrup", None)), Some(169661));
+        assert_eq!(mapping.fdc_id_for(&food(7, "Butter", None)), Some(173410));
+    }
+
+    #[test]
+    fn ids_take_precedence_over_names() {
+        let mapping = FoodMapping {
+            by_id: HashMap::from([(42, 169661)]),
+            by_name: HashMap::from([("Butter".to_string(), 173410)]),
+        };
+        assert_eq!(mapping.fdc_id_for(&food(42, "Butter", None)), Some(169661));
+        assert_eq!(mapping.fdc_id_for(&food(7, "Butter", None)), Some(173410));
+        assert_eq!(mapping.fdc_id_for(&food(7, "butter", None)), None);
+    }
+
+    #[test]
+    fn precedence_decides_between_tandoor_and_the_file() {
+        let mapping = FoodMapping { by_id: HashMap::from([(42, 169661)]), by_name: HashMap::new() };
+        let food_with_fdc_id = food(42, "Maple syrup", Some(111111));
+        assert_eq!(resolve_fdc_id(&food_with_fdc_id, Some(&mapping), MappingPrecedence::Tandoor), Some(111111));
+        assert_eq!(resolve_fdc_id(&food_with_fdc_id, Some(&mapping), MappingPrecedence::File), Some(169661));
+        assert_eq!(resolve_fdc_id(&food(42, "Maple syrup", None), Some(&mapping), MappingPrecedence::Tandoor), Some(169661));
+        assert_eq!(resolve_fdc_id(&food(7, "Butter", Some(111111)), Some(&mapping), MappingPrecedence::File), Some(111111));
+    }
+
+    #[test]
+    fn rejects_unknown_extensions() {
+        assert!(MappingFormat::from_path(Path::new("mapping.txt")).is_err());
+        assert_eq!(MappingFormat::from_path(Path::new("mapping.YML")).unwrap(), MappingFormat::Yaml);
+    }
+}
@@ -0,0 +1,163 @@
+//! Imports the portions of FDC foods (e.g. 1 cup = 240 g) as unit conversions of the Tandoor foods.
+//!
+//! Tandoor can only calculate the nutrients of an ingredient measured in cups or pieces if the food has a conversion
+//! from that unit to a mass. Portions are only imported for units the Tandoor instance already has, as recipes can
+//! only use those.
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+use log::{debug, info};
+use crate::gram_unit::GramUnit;
+use crate::models::tandoor::api_tandoor_food_reference::ApiTandoorFoodReference;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+use crate::models::tandoor::api_tandoor_unit_conversion::ApiTandoorUnitConversion;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+use crate::models::usda::usda_food::USDAFood;
+use crate::tandoor_client::TandoorClient;
+use crate::units;
+
+/// How much the grams of a present conversion may differ from the FDC portion to count as the same conversion.
+const RELATIVE_TOLERANCE: f32 = 0.01;
+
+/// What happened to the portions of a food.
+#[derive(Debug, Default)]
+pub struct PortionImport {
+    /// The number of conversions created.
+    pub created: usize,
+    /// The number of present conversions that were updated.
+    pub updated: usize,
+}
+
+/// Imports FDC portions as Tandoor unit conversions.
+pub struct PortionImporter {
+    units: Vec<ApiTandoorUnit>,
+    unit_mapping: HashMap<String, String>,
+    gram_unit: Arc<GramUnit>,
+    overwrite: bool,
+    dry_run: bool,
+}
+
+impl PortionImporter {
+    /// Creates the importer.
+    /// ### Parameters
+    /// - units: All units of the Tandoor instance.
+    /// - unit_mapping: Names of Tandoor units per name of an FDC measure unit, for units named differently.
+    /// - gram_unit: The unit the gram weights of the portions are given in. Only resolved if a portion is imported.
+    /// - overwrite: When set present conversions to another amount of grams are updated.
+    /// - dry_run: When set the conversions are only logged.
+    pub fn new(units: Vec<ApiTandoorUnit>, unit_mapping: &HashMap<String, String>, gram_unit: Arc<GramUnit>, overwrite: bool, dry_run: bool) -> Self {
+        PortionImporter {
+            units,
+            unit_mapping: unit_mapping
+                .iter()
+                .map(|(fdc_unit, tandoor_unit)| (fdc_unit.to_lowercase(), tandoor_unit.to_string()))
+                .collect(),
+            gram_unit,
+            overwrite,
+            dry_run,
+        }
+    }
+
+    /// Creates or updates the unit conversions of a food from the portions of its FDC food.
+    /// ### Parameters
+    /// - tandoor_client: The client used to interact with Tandoor.
+    /// - food: The Tandoor food.
+    /// - usda_food: The FDC food holding the portions.
+    /// ### Returns
+    /// How many conversions were created and updated.
+    /// ### Remarks
+    /// Only the first portion of every Tandoor unit is imported. Conversions that are already present are skipped.
+    pub async fn import(&self, tandoor_client: &TandoorClient, food: &InternalTandoorFood, usda_food: &USDAFood) -> Result<PortionImport, Box<dyn Error>> {
+        let mut portions: Vec<(f32, &ApiTandoorUnit, f32)> = Vec::new();
+        for portion in &usda_food.food_portions {
+            let (Some((amount, fdc_unit)), Some(grams)) = (portion.measure(), portion.gram_weight) else {
+                continue;
+            };
+            let Some(unit) = self.tandoor_unit(&fdc_unit) else {
+                debug!("Skipping portion {} {} of food {} as Tandoor has no matching unit.", amount, fdc_unit, food.name);
+                continue;
+            };
+            // Masses are already converted by Tandoor itself.
+            let is_mass = units::convert(1.0, &unit.name, units::GRAM, 0).is_some();
+            if amount <= 0.0 || grams <= 0.0 || is_mass || portions.iter().any(|(_, u, _)| u.is_same_unit(unit)) {
+                continue;
+            }
+            portions.push((amount, unit, grams));
+        }
+        if portions.is_empty() {
+            return Ok(PortionImport::default());
+        }
+
+        let gram_unit = self.gram_unit.get().await?;
+        let conversions = portions
+            .into_iter()
+            .map(|(amount, unit, grams)| ApiTandoorUnitConversion {
+                id: None,
+                base_amount: amount,
+                base_unit: unit.clone(),
+                converted_amount: grams,
+                converted_unit: gram_unit.clone(),
+                food: Some(ApiTandoorFoodReference { id: food.id, name: food.name.to_string() }),
+            });
+
+        let present_conversions = tandoor_client.get_unit_conversions(&food.id).await?;
+        let mut portion_import = PortionImport::default();
+        for conversion in conversions {
+            let present_conversion = present_conversions.iter().find_map(|present| {
+                grams_per_unit(present, &conversion.base_unit).map(|grams| (present, grams))
+            });
+            let grams = conversion.converted_amount / conversion.base_amount;
+            match present_conversion {
+                Some((_, present_grams)) if (present_grams - grams).abs() <= grams * RELATIVE_TOLERANCE => {
+                    debug!("Food {} already has a conversion of {} to {} g.", food.name, conversion.base_unit.name, present_grams);
+                }
+                Some((present, present_grams)) => {
+                    let (true, Some(id)) = (self.overwrite, present.id) else {
+                        debug!("Keeping the conversion of {} of food {} to {} g as it differs from the FDC portion ({} g).", conversion.base_unit.name, food.name, present_grams, grams);
+                        continue;
+                    };
+                    if self.dry_run {
+                        info!("Would update the conversion of food {} to {} {} = {} {}", food.name, conversion.base_amount, conversion.base_unit.name, conversion.converted_amount, conversion.converted_unit.name);
+                    } else {
+                        tandoor_client.update_unit_conversion(&id, &conversion).await?;
+                        info!("Updated the conversion of food {} to {} {} = {} {}", food.name, conversion.base_amount, conversion.base_unit.name, conversion.converted_amount, conversion.converted_unit.name);
+                    }
+                    portion_import.updated += 1;
+                }
+                None => {
+                    if self.dry_run {
+                        info!("Would add the conversion {} {} = {} {} to food {}", conversion.base_amount, conversion.base_unit.name, conversion.converted_amount, conversion.converted_unit.name, food.name);
+                    } else {
+                        tandoor_client.create_unit_conversion(&conversion).await?;
+                        info!("Added the conversion {} {} = {} {} to food {}", conversion.base_amount, conversion.base_unit.name, conversion.converted_amount, conversion.converted_unit.name, food.name);
+                    }
+                    portion_import.created += 1;
+                }
+            }
+        }
+        Ok(portion_import)
+    }
+
+    /// Finds the Tandoor unit of an FDC measure unit, using the mapping first and the name of the unit otherwise.
+    fn tandoor_unit(&self, fdc_unit: &str) -> Option<&ApiTandoorUnit> {
+        let name = self.unit_mapping.get(fdc_unit).map_or(fdc_unit, String::as_str);
+        self.units.iter().find(|unit| unit.is_named(name))
+    }
+}
+
+/// Gets how many grams one of the given unit weighs according to a unit conversion.
+/// ### Parameters
+/// - conversion: The unit conversion.
+/// - unit: The unit to get the grams of.
+/// ### Returns
+/// The grams or None if the conversion is not between the unit and a mass.
+pub fn grams_per_unit(conversion: &ApiTandoorUnitConversion, unit: &ApiTandoorUnit) -> Option<f32> {
+    let (unit_amount, grams) = if conversion.base_unit.is_same_unit(unit) {
+        (conversion.base_amount, units::convert(conversion.converted_amount, &conversion.converted_unit.name, units::GRAM, 0)?)
+    } else if conversion.converted_unit.is_same_unit(unit) {
+        (conversion.converted_amount, units::convert(conversion.base_amount, &conversion.base_unit.name, units::GRAM, 0)?)
+    } else {
+        return None;
+    };
+    (unit_amount > 0.0).then(|| grams / unit_amount)
+}
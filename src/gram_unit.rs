@@ -0,0 +1,54 @@
+//! Provides the unit the values of the FDC database refer to.
+//!
+//! The unit is only looked up (and created if Tandoor does not have it) the first time a food needs it, so runs that
+//! do not update any food do not change the units of the Tandoor instance.
+use std::error::Error;
+use std::sync::Arc;
+use log::{debug, info};
+use tokio::sync::OnceCell;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+use crate::tandoor_client::TandoorClient;
+use crate::units;
+
+/// The gram unit of the Tandoor instance, resolved on first use.
+pub struct GramUnit {
+    tandoor_client: Arc<TandoorClient>,
+    dry_run: bool,
+    unit: OnceCell<ApiTandoorUnit>,
+}
+
+impl GramUnit {
+    /// Creates the gram unit without contacting Tandoor.
+    /// ### Parameters
+    /// - tandoor_client: The client used to interact with Tandoor.
+    /// - dry_run: When set a missing unit is not created.
+    pub fn new(tandoor_client: Arc<TandoorClient>, dry_run: bool) -> Self {
+        GramUnit {
+            tandoor_client,
+            dry_run,
+            unit: OnceCell::new(),
+        }
+    }
+
+    /// Gets the gram unit, looking it up and creating it if Tandoor does not have it on the first call.
+    /// ### Returns
+    /// The unit. In a dry run the unit has no id if it does not exist yet.
+    pub async fn get(&self) -> Result<&ApiTandoorUnit, Box<dyn Error>> {
+        self.unit.get_or_try_init(|| self.find_or_create()).await
+    }
+
+    /// Looks up the gram unit and creates it if Tandoor does not have it.
+    async fn find_or_create(&self) -> Result<ApiTandoorUnit, Box<dyn Error>> {
+        if let Some(unit) = self.tandoor_client.find_unit(units::GRAM).await? {
+            debug!("Found unit {} with ID {:?}", unit.name, unit.id);
+            return Ok(unit);
+        }
+        if self.dry_run {
+            info!("The unit {} would be created.", units::GRAM);
+            return Ok(ApiTandoorUnit { id: None, name: units::GRAM.to_string(), plural_name: None });
+        }
+        let unit = self.tandoor_client.create_unit(units::GRAM).await?;
+        info!("Created unit {} as Tandoor did not have it.", unit.name);
+        Ok(unit)
+    }
+}
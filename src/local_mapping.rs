@@ -0,0 +1,87 @@
+//! Remembers the decisions the user made in interactive mode (picked FDC IDs and skips) across runs.
+//!
+//! The mapping is a JSON file holding one [LocalMappingEntry] per food. It is rewritten after every decision,
+//! so nothing is lost if the program stops in the middle of a run.
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::models::local_mapping_entry::LocalMappingEntry;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+
+/// The decisions made in interactive mode.
+pub struct LocalMapping {
+    path: PathBuf,
+    entries: Mutex<Vec<LocalMappingEntry>>,
+}
+
+impl LocalMapping {
+    /// Loads the mapping from a file. A missing file is treated as an empty mapping.
+    /// ### Parameters
+    /// - path: The path of the mapping file.
+    /// ### Returns
+    /// The mapping or an error if the file exists but could not be read.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let entries = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(LocalMapping {
+            path: path.to_path_buf(),
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Checks whether the user asked to never be asked about the food again.
+    /// ### Parameters
+    /// - food_id: The id of the food.
+    pub fn is_skipped(&self, food_id: &i32) -> bool {
+        self.entries.lock().unwrap().iter().any(|e| e.food_id == *food_id && e.skipped)
+    }
+
+    /// Gets the FDC ID the user picked for the food.
+    /// ### Parameters
+    /// - food_id: The id of the food.
+    pub fn fdc_id_for(&self, food_id: &i32) -> Option<i32> {
+        self.entries.lock().unwrap().iter().find(|e| e.food_id == *food_id).and_then(|e| e.fdc_id)
+    }
+
+    /// Remembers the FDC ID the user picked for the food and saves the mapping.
+    /// ### Parameters
+    /// - food: The food.
+    /// - fdc_id: The picked FDC ID.
+    pub fn remember_fdc_id(&self, food: &InternalTandoorFood, fdc_id: &i32) -> Result<(), Box<dyn Error>> {
+        self.remember(LocalMappingEntry {
+            food_id: food.id,
+            food_name: food.name.to_string(),
+            fdc_id: Some(*fdc_id),
+            skipped: false,
+        })
+    }
+
+    /// Remembers that the user never wants to be asked about the food again and saves the mapping.
+    /// ### Parameters
+    /// - food: The skipped food.
+    pub fn remember_skip(&self, food: &InternalTandoorFood) -> Result<(), Box<dyn Error>> {
+        self.remember(LocalMappingEntry {
+            food_id: food.id,
+            food_name: food.name.to_string(),
+            fdc_id: None,
+            skipped: true,
+        })
+    }
+
+    /// Replaces the entry of the food and saves the mapping.
+    fn remember(&self, entry: LocalMappingEntry) -> Result<(), Box<dyn Error>> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|e| e.food_id != entry.food_id);
+        entries.push(entry);
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&*entries)?)?;
+        Ok(())
+    }
+}
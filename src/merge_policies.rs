@@ -0,0 +1,74 @@
+//! Decides per property whether existing values of a food are overwritten.
+use std::collections::HashMap;
+use crate::models::merge_policy::MergePolicy;
+use crate::models::merge_policy_override::MergePolicyOverride;
+
+/// The merge policies of all properties.
+pub struct MergePolicies {
+    default: MergePolicy,
+    per_property: HashMap<String, MergePolicy>,
+}
+
+impl MergePolicies {
+    /// Combines the configured policies with the policies given for this run.
+    /// ### Parameters
+    /// - default: The policy of properties without a policy of their own.
+    /// - per_property: The policies configured per property name.
+    /// - overrides: The policies given on the command line. They take precedence over the configured policies,
+    ///   a policy without property replaces the default policy. Policies of single properties are kept.
+    pub fn new(default: MergePolicy, per_property: &HashMap<String, MergePolicy>, overrides: &[MergePolicyOverride]) -> Self {
+        let mut policies = MergePolicies {
+            default,
+            per_property: per_property.clone(),
+        };
+        for policy_override in overrides {
+            match &policy_override.property {
+                Some(property) => {
+                    policies.per_property.insert(property.to_string(), policy_override.policy);
+                }
+                None => {
+                    policies.default = policy_override.policy;
+                }
+            }
+        }
+        policies
+    }
+
+    /// Gets the policy of a property.
+    /// ### Parameters
+    /// - property: The name of the property in Tandoor.
+    pub fn policy_for(&self, property: &str) -> MergePolicy {
+        self.per_property.get(property).copied().unwrap_or(self.default)
+    }
+
+    /// Checks whether any existing value might be overwritten.
+    /// If not, foods that already have all properties do not need to be updated.
+    pub fn can_overwrite(&self) -> bool {
+        self.default != MergePolicy::Never || self.per_property.values().any(|p| *p != MergePolicy::Never)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn override_of(property: Option<&str>, policy: MergePolicy) -> MergePolicyOverride {
+        MergePolicyOverride { property: property.map(str::to_string), policy }
+    }
+
+    #[test]
+    fn global_overrides_keep_the_policies_of_single_properties() {
+        let configured = HashMap::from([("Sodium".to_string(), MergePolicy::Never)]);
+        let policies = MergePolicies::new(MergePolicy::IfEmpty, &configured, &[override_of(None, MergePolicy::Always)]);
+        assert_eq!(policies.policy_for("Sodium"), MergePolicy::Never);
+        assert_eq!(policies.policy_for("Fat"), MergePolicy::Always);
+    }
+
+    #[test]
+    fn property_overrides_replace_configured_policies() {
+        let configured = HashMap::from([("Sodium".to_string(), MergePolicy::Never)]);
+        let policies = MergePolicies::new(MergePolicy::Never, &configured, &[override_of(Some("Sodium"), MergePolicy::IfEmpty)]);
+        assert_eq!(policies.policy_for("Sodium"), MergePolicy::IfEmpty);
+        assert!(policies.can_overwrite());
+    }
+}
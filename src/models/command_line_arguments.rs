@@ -1,22 +1,242 @@
 //! Holds all the command line parameters and the types associated with them
-use clap::Parser;
+use std::path::PathBuf;
+use chrono::NaiveDate;
+use clap::{ArgMatches, Args as _, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
 use log::LevelFilter;
+use regex::Regex;
+use crate::models::merge_policy_override::MergePolicyOverride;
+use crate::models::usda::usda_data_type::USDADataType;
 
 /// Struct containing all possible command line parameters.
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args{
+    /// Log level
+    #[arg(short, long, global = true, default_value = "info", help = "Sets the log level.",)]
+    pub log_level: LogLevel,
+
+    /// Configuration file
+    #[arg(long, global = true, default_value = "./appsettings.json", help = "The configuration file to use.")]
+    pub config: PathBuf,
+
+    /// Command to run, updating the foods when none is given
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Options of the sync command used when no command is given
+    #[command(flatten)]
+    pub sync: SyncArgs,
+}
+
+impl Args{
+    /// Parses the command line arguments and exits with a usage error if they are invalid.
+    /// ### Remarks
+    /// The options of the sync command may be given without a command, so they cannot be rejected by clap when
+    /// another command is given. They are checked here instead, while the global options work with every command.
+    pub fn parse_command_line() -> Self {
+        let mut command = Args::command();
+        let matches = command.get_matches_mut();
+        let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.format(&mut command).exit());
+        if let (Some(_), Some(option)) = (&args.command, given_sync_option(&matches)) {
+            command.error(ErrorKind::ArgumentConflict, format!("the option '{}' can only be used with the sync command or without a command", option)).exit();
+        }
+        args
+    }
+}
+
+/// Finds an option of the sync command that was given on the command line before the command.
+fn given_sync_option(matches: &ArgMatches) -> Option<String> {
+    SyncArgs::augment_args(clap::Command::new("sync"))
+        .get_arguments()
+        .find(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+        .map(|arg| arg.get_long().map_or_else(|| arg.get_id().to_string(), |long| format!("--{}", long)))
+}
+
+/// Commands offered by the program.
+#[derive(Subcommand, Debug)]
+pub enum Command{
+    /// Updates the properties of the Tandoor foods with data from the FDC database. This is the default command.
+    Sync(SyncArgs),
+    /// Checks the configuration, the connections to Tandoor and the FDC database and reports foods that cannot be updated.
+    Check,
+    /// Searches the FDC database for foods to find their FDC ID.
+    Search(SearchArgs),
+    /// Shows a single food with its properties.
+    Show{
+        /// The ID or the name of the food.
+        food: String,
+    },
+    /// Exports all foods with their properties.
+    Export(ExportArgs),
+    /// Exports the FDC IDs of all foods to a mapping file.
+    ExportMapping{
+        /// The mapping file to write. The extension (.csv, .yaml, .yml or .json) selects the format.
+        output: PathBuf,
+    },
+    /// Restores the properties of all foods saved in a snapshot.
+    Restore{
+        /// The snapshot file written by a previous run.
+        snapshot: PathBuf,
+    },
+    /// Removes expired entries from the FDC cache.
+    PruneCache,
+    /// Creates the property types of a preset that are missing in Tandoor.
+    InitProperties(InitPropertiesArgs),
+}
+
+/// Options of the sync command.
+#[derive(clap::Args, Debug)]
+pub struct SyncArgs{
     /// Override existing properties
-    #[arg(short, long="override", help = "When set the program overrides already present properties.")]
+    #[arg(short, long="override", help = "When set the program overrides already present properties, except those with a merge policy of their own. Same as --merge-policy always.")]
     pub override_properties: bool,
 
+    /// Merge policies
+    #[arg(long = "merge-policy", value_name = "[PROPERTY=]POLICY", help = "When present properties are overwritten (never, always, if-empty or if-differs:<tolerance>), for all properties or the given one. Overrides the appsettings. Can be given multiple times.")]
+    pub merge_policies: Vec<MergePolicyOverride>,
+
     /// Interactive mode
-    #[arg(short, long, help = "When set the program asks the user to provide an FDC ID when none was found.")]
+    #[arg(short, long, help = "When set the program lists FDC candidates for foods without an FDC ID. Pick one by its number, paste an FDC URL or ID (prefix IDs with fewer than 6 digits with id:), enter a new search, leave empty to skip or enter ! to never be asked again.")]
     pub interactive: bool,
 
-    /// Log level
-    #[arg(short, long, default_value = "info", help = "Sets the log level.",)]
-    pub log_level: LogLevel,
+    /// Concurrency
+    #[arg(short, long, help = "How many foods are processed at the same time. Overrides the appsettings.")]
+    pub concurrency: Option<usize>,
+
+    /// Tandoor concurrency
+    #[arg(long, help = "How many updates are sent to Tandoor at the same time. Overrides the appsettings.")]
+    pub tandoor_concurrency: Option<usize>,
+
+    /// FDC concurrency
+    #[arg(long, help = "How many requests are sent to the FDC database at the same time. Overrides the appsettings.")]
+    pub fdc_concurrency: Option<usize>,
+
+    /// Auto-match
+    #[arg(long, help = "When set the program searches the FDC database by name for foods without an FDC ID and links the best match.")]
+    pub auto_match: bool,
+
+    /// Match threshold
+    #[arg(long, value_name = "SCORE", help = "The score between 0 and 1 a match needs to be linked automatically. Overrides the appsettings.")]
+    pub match_threshold: Option<f32>,
+
+    /// Dry run
+    #[arg(long, help = "When set the program prints the changes it would make to every food without updating it.")]
+    pub dry_run: bool,
+
+    /// Diff output
+    #[arg(long, help = "Writes the changes made (or that would be made in a dry run) to every food as JSON to the given file.")]
+    pub diff_output: Option<PathBuf>,
+
+    /// Strict mode
+    #[arg(long, help = "When set the program stops if not all foods could be loaded from Tandoor.")]
+    pub strict: bool,
+
+    /// Refresh cache
+    #[arg(long, help = "When set cached FDC responses are ignored and fetched again.")]
+    pub refresh_cache: bool,
+
+    /// Import portions
+    #[arg(long, help = "When set the portions of the FDC foods (e.g. 1 cup = 240 g) are added to the foods as unit conversions. Present conversions are only changed with --override. Conversions are not saved to snapshots, so restore does not revert them.")]
+    pub import_portions: bool,
+
+    /// Food IDs
+    #[arg(long = "id", value_name = "ID", help = "Only updates the food with the given ID. Can be given multiple times.")]
+    pub food_ids: Vec<i32>,
+
+    /// Food names
+    #[arg(long = "name", value_name = "NAME", help = "Only updates the food with exactly the given name. Can be given multiple times.")]
+    pub food_names: Vec<String>,
+
+    /// Name patterns
+    #[arg(long = "name-pattern", value_name = "REGEX", help = "Only updates foods whose name matches the regular expression. Can be given multiple times.")]
+    pub name_patterns: Vec<Regex>,
+
+    /// Modified since
+    #[arg(long, value_name = "DATE", help = "Only updates foods modified on or after the given day (YYYY-MM-DD). Foods without modification time are left out.")]
+    pub modified_since: Option<NaiveDate>,
+
+    /// Exclude patterns
+    #[arg(long = "exclude", value_name = "REGEX", help = "Never updates foods whose name matches the regular expression. Can be given multiple times.")]
+    pub exclude_patterns: Vec<Regex>,
+}
+
+/// Options of the search command.
+#[derive(clap::Args, Debug)]
+pub struct SearchArgs{
+    /// The search terms.
+    pub query: String,
+
+    /// Data types
+    #[arg(short, long = "data-type", value_name = "DATA_TYPE", help = "Only lists foods of the given data type. Can be given multiple times.")]
+    pub data_types: Vec<USDADataType>,
+
+    /// Page
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), help = "The page of results to list.")]
+    pub page: u32,
+
+    /// Page size
+    #[arg(long, default_value_t = 25, value_parser = clap::value_parser!(u32).range(1..=200), help = "How many results are listed per page.")]
+    pub page_size: u32,
+}
+
+/// Options of the export command.
+#[derive(clap::Args, Debug)]
+pub struct ExportArgs{
+    /// Output file
+    #[arg(short, long, help = "The file to write the foods to. The foods are printed when not set.")]
+    pub output: Option<PathBuf>,
+
+    /// Export format
+    #[arg(short, long, default_value = "json", help = "The format of the export.")]
+    pub format: ExportFormat,
+}
+
+/// Possible formats of an export.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum ExportFormat{
+    /// A JSON array of all foods with their properties.
+    Json,
+    /// One row per food with one column per property.
+    Csv,
+}
+
+/// Options of the init-properties command.
+#[derive(clap::Args, Debug)]
+pub struct InitPropertiesArgs{
+    /// The preset of property types to create.
+    pub preset: PropertyPreset,
+
+    /// Language
+    #[arg(long, default_value = "en", help = "The language of the names of the created property types.")]
+    pub language: Language,
+
+    /// Dry run
+    #[arg(long, help = "When set the program prints the property types it would create without creating them.")]
+    pub dry_run: bool,
+}
+
+/// Built-in sets of property types.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum PropertyPreset{
+    /// Energy, fat, carbohydrates, sugars, fiber and protein.
+    BasicMacros,
+    /// The nutrition declaration of EU food labels.
+    EuLabel,
+    /// The EU label together with fatty acids, cholesterol, minerals and vitamins.
+    FullMicronutrients,
+}
+
+/// Languages the names of preset property types are available in.
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Language{
+    /// English
+    En,
+    /// German
+    De,
+    /// French
+    Fr,
 }
 
 /// Possible log levels.
@@ -29,9 +249,9 @@ pub enum LogLevel{
     Error
 }
 
-impl Into<LevelFilter> for LogLevel{
-    fn into(self) -> LevelFilter {
-        match self {
+impl From<LogLevel> for LevelFilter{
+    fn from(value: LogLevel) -> Self {
+        match value {
             LogLevel::Trace => LevelFilter::Trace,
             LogLevel::Debug => LevelFilter::Debug,
             LogLevel::Info => LevelFilter::Info,
@@ -39,4 +259,4 @@ impl Into<LevelFilter> for LogLevel{
             LogLevel::Error => LevelFilter::Error
         }
     }
-}
\ No newline at end of file
+}
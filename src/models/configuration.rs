@@ -1,16 +1,141 @@
 //! Holds the configuration of the program.
+use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
+use crate::models::mapping_precedence::MappingPrecedence;
+use crate::models::merge_policy::MergePolicy;
+use crate::models::usda::usda_data_type::USDADataType;
 
 /// Holds the configuration of the program.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Configuration{
-    /// The Tandoor version that is used (either v1 or v2)
-    pub tandoor_version: String,
+    /// The Tandoor version that is used (either v1 or v2).
+    /// If not set the version is detected automatically.
+    pub tandoor_version: Option<String>,
     /// The API key used to access Tandoor resources.
     pub tandoor_api_key: String,
-    /// The URL of the Tandoor endpoint.
+    /// The URL of the Tandoor endpoint including scheme, port and path prefix (e.g. https://example.com/tandoor).
+    /// If no scheme is given http is used.
     pub tandoor_url: String,
     /// The API key used to access USDA FDC resources.
-    pub usda_api_key: String
-}
\ No newline at end of file
+    pub usda_api_key: String,
+    /// Path to a PEM file containing additional CA certificates that should be trusted.
+    pub tls_ca_certificate: Option<String>,
+    /// Path to a PEM file containing the client certificate and its private key.
+    pub tls_client_certificate: Option<String>,
+    /// Accept invalid certificates (e.g. self-signed without a CA). Only use this if you know what you are doing.
+    #[serde(default)]
+    pub tls_accept_invalid_certificates: bool,
+    /// The directory FDC responses are cached in.
+    #[serde(default = "default_fdc_cache_directory")]
+    pub fdc_cache_directory: String,
+    /// How many hours a cached FDC response is used before it is fetched again.
+    #[serde(default = "default_fdc_cache_ttl_hours")]
+    pub fdc_cache_ttl_hours: u64,
+    /// The directory snapshots of foods are stored in before the foods are updated.
+    #[serde(default = "default_snapshot_directory")]
+    pub snapshot_directory: String,
+    /// How many foods are processed at the same time.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// How many updates are sent to Tandoor at the same time.
+    #[serde(default = "default_tandoor_concurrency")]
+    pub tandoor_concurrency: usize,
+    /// How many requests are sent to the FDC database at the same time.
+    #[serde(default = "default_fdc_concurrency")]
+    pub fdc_concurrency: usize,
+    /// How often a request that failed due to a transient error (e.g. a 502 or a connection reset) is retried.
+    #[serde(default = "default_retry_attempts")]
+    pub retry_attempts: u32,
+    /// The delay in milliseconds before the first retry. The delay doubles with every retry.
+    #[serde(default = "default_retry_base_delay_milliseconds")]
+    pub retry_base_delay_milliseconds: u64,
+    /// The maximum delay in milliseconds between two retries.
+    #[serde(default = "default_retry_max_delay_milliseconds")]
+    pub retry_max_delay_milliseconds: u64,
+    /// Names or IDs of foods that are never updated.
+    #[serde(default)]
+    pub ignored_foods: Vec<String>,
+    /// The score between 0 and 1 a food found by the automatic matching needs to be linked.
+    #[serde(default = "default_auto_match_threshold")]
+    pub auto_match_threshold: f32,
+    /// The data types searched by the automatic matching, most preferred first.
+    #[serde(default = "default_auto_match_data_types")]
+    pub auto_match_data_types: Vec<USDADataType>,
+    /// The file the candidates of foods that could not be matched automatically are written to.
+    #[serde(default = "default_auto_match_review_file")]
+    pub auto_match_review_file: String,
+    /// The file the decisions made in interactive mode are remembered in.
+    #[serde(default = "default_local_mapping_file")]
+    pub local_mapping_file: String,
+    /// A CSV, YAML or JSON file linking foods (by ID or name) to FDC IDs.
+    pub mapping_file: Option<String>,
+    /// Whether the mapping file or the fields of the foods in Tandoor are used first.
+    #[serde(default)]
+    pub mapping_precedence: MappingPrecedence,
+    /// When existing values of properties without a policy of their own are overwritten.
+    #[serde(default = "default_merge_policy")]
+    pub default_merge_policy: MergePolicy,
+    /// When existing values are overwritten, per name of the property in Tandoor.
+    #[serde(default)]
+    pub merge_policies: HashMap<String, MergePolicy>,
+    /// Names of Tandoor units per name of an FDC measure unit, for portions whose unit is named differently in Tandoor.
+    #[serde(default)]
+    pub portion_units: HashMap<String, String>,
+}
+
+fn default_fdc_cache_directory() -> String {
+    "./fdc_cache".to_string()
+}
+
+fn default_fdc_cache_ttl_hours() -> u64 {
+    24 * 30
+}
+
+fn default_snapshot_directory() -> String {
+    "./snapshots".to_string()
+}
+
+fn default_concurrency() -> usize {
+    64
+}
+
+fn default_tandoor_concurrency() -> usize {
+    4
+}
+
+fn default_fdc_concurrency() -> usize {
+    2
+}
+
+fn default_retry_attempts() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_milliseconds() -> u64 {
+    500
+}
+
+fn default_retry_max_delay_milliseconds() -> u64 {
+    30_000
+}
+
+fn default_auto_match_threshold() -> f32 {
+    0.85
+}
+
+fn default_auto_match_data_types() -> Vec<USDADataType> {
+    vec![USDADataType::Foundation, USDADataType::SrLegacy, USDADataType::Survey]
+}
+
+fn default_auto_match_review_file() -> String {
+    "./auto_match_review.json".to_string()
+}
+
+fn default_local_mapping_file() -> String {
+    "./fdc_mapping.json".to_string()
+}
+
+fn default_merge_policy() -> MergePolicy {
+    MergePolicy::Never
+}
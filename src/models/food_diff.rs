@@ -0,0 +1,116 @@
+//! Holds all changes an update makes to the properties of a food.
+use serde::{Serialize, Deserialize};
+use crate::models::property_change::PropertyChange;
+use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FoodDiff{
+    /// The id of the food.
+    pub food_id: i32,
+    /// The name of the food.
+    pub food_name: String,
+    /// The FDC ID the new values are taken from.
+    pub fdc_id: Option<i32>,
+    /// All properties that are added, changed or removed.
+    pub changes: Vec<PropertyChange>,
+    /// The amount of the food the values referred to before, if it changes (e.g. 1 piece).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_base: Option<String>,
+    /// The amount of the food the values refer to after the update, if it changes (e.g. 100 g).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_base: Option<String>,
+}
+
+impl FoodDiff{
+    /// Compares the food as it is in Tandoor with the food that is going to be sent to Tandoor.
+    /// ### Parameters
+    /// - original: The food as it is in Tandoor.
+    /// - updated: The food that is going to be sent to Tandoor.
+    /// ### Returns
+    /// The diff containing only the properties that differ.
+    pub fn new(original: &InternalTandoorFood, updated: &ApiTandoorFood) -> Self {
+        let mut changes: Vec<PropertyChange> = Vec::new();
+
+        for new_property in &updated.properties {
+            let old_amount = original.properties
+                .iter()
+                .find(|p| p.property_type.name == new_property.property_type.name)
+                .and_then(|p| p.property_amount);
+            if old_amount != Some(new_property.property_amount) {
+                changes.push(PropertyChange {
+                    property: new_property.property_type.name.to_string(),
+                    old_amount,
+                    new_amount: Some(new_property.property_amount),
+                    fdc_nutrient_id: new_property.property_type.fdc_id,
+                });
+            }
+        }
+
+        for old_property in &original.properties {
+            if !updated.properties.iter().any(|p| p.property_type.name == old_property.property_type.name) {
+                changes.push(PropertyChange {
+                    property: old_property.property_type.name.to_string(),
+                    old_amount: old_property.property_amount,
+                    new_amount: None,
+                    fdc_nutrient_id: None,
+                });
+            }
+        }
+
+        let old_base = format_base(original.properties_food_amount, original.properties_food_unit.as_ref());
+        let new_base = format_base(updated.properties_food_amount, updated.properties_food_unit.as_ref());
+        let is_base_changed = old_base != new_base;
+
+        FoodDiff {
+            food_id: original.id,
+            food_name: original.name.to_string(),
+            fdc_id: updated.fdc_id,
+            changes,
+            old_base: old_base.filter(|_| is_base_changed),
+            new_base: new_base.filter(|_| is_base_changed),
+        }
+    }
+
+    /// Renders the diff as table readable by humans.
+    pub fn to_table(&self) -> String {
+        let mut table = format!("{} (ID {}) using FDC ID {}\n",
+            self.food_name,
+            self.food_id,
+            self.fdc_id.map_or("-".to_string(), |id| id.to_string()));
+        let is_base_changed = self.old_base.is_some() || self.new_base.is_some();
+        if is_base_changed {
+            table.push_str(&format!("  Values per {} instead of {}\n",
+                self.new_base.as_deref().unwrap_or("-"),
+                self.old_base.as_deref().unwrap_or("-")));
+        }
+        if self.changes.is_empty() {
+            if !is_base_changed {
+                table.push_str("  No changes\n");
+            }
+            return table;
+        }
+
+        let width = self.changes.iter().map(|c| c.property.chars().count()).max().unwrap_or_default().max("Property".len());
+        table.push_str(&format!("  {:<width$}  {:>12}  {:>12}  {:>12}\n", "Property", "Old amount", "New amount", "FDC nutrient"));
+        for change in &self.changes {
+            table.push_str(&format!("  {:<width$}  {:>12}  {:>12}  {:>12}\n",
+                change.property,
+                format_amount(change.old_amount),
+                format_amount(change.new_amount),
+                change.fdc_nutrient_id.map_or("-".to_string(), |id| id.to_string())));
+        }
+        table
+    }
+}
+
+/// Formats the amount of a food the values refer to (e.g. 100 g). None if the food has no unit set.
+fn format_base(amount: Option<f32>, unit: Option<&ApiTandoorUnit>) -> Option<String> {
+    unit.map(|unit| format!("{} {}", amount.unwrap_or_default(), unit.name))
+}
+
+fn format_amount(amount: Option<f32>) -> String {
+    amount.map_or("-".to_string(), |a| a.to_string())
+}
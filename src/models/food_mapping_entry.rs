@@ -0,0 +1,13 @@
+//! Links a Tandoor food to an FDC food in a mapping file.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FoodMappingEntry{
+    /// The id of the food. Takes precedence over the name if both are set.
+    pub food_id: Option<i32>,
+    /// The exact name of the food.
+    pub food_name: Option<String>,
+    /// The FDC ID of the food.
+    pub fdc_id: i32,
+}
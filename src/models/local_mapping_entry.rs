@@ -0,0 +1,17 @@
+//! Holds what the user decided for a food in interactive mode.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalMappingEntry{
+    /// The id of the food.
+    pub food_id: i32,
+    /// The name of the food. Only kept to make the file readable.
+    pub food_name: String,
+    /// The FDC ID the user picked for the food.
+    #[serde(default)]
+    pub fdc_id: Option<i32>,
+    /// Whether the user asked to never be asked about this food again.
+    #[serde(default)]
+    pub skipped: bool,
+}
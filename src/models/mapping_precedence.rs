@@ -0,0 +1,12 @@
+//! Holds which source of FDC IDs is used first.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum MappingPrecedence{
+    /// The URL and FDC ID fields of the food in Tandoor are used first, the mapping file only fills gaps.
+    #[default]
+    Tandoor,
+    /// The mapping file is used first, the fields of the food in Tandoor only fill gaps.
+    File,
+}
@@ -0,0 +1,34 @@
+//! Represents an FDC food that might match a Tandoor food.
+use serde::{Serialize, Deserialize};
+use crate::models::usda::usda_search_result::USDASearchResult;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchCandidate{
+    /// The FDC ID of the food.
+    pub fdc_id: i32,
+    /// The description of the food in the FDC database.
+    pub description: String,
+    /// The data type of the food (e.g. Foundation or Branded).
+    pub data_type: Option<String>,
+    /// The brand of branded foods.
+    pub brand: Option<String>,
+    /// How well the food matches between 0 and 1.
+    pub score: f32,
+}
+
+impl MatchCandidate{
+    /// Creates a candidate from a search result.
+    /// ### Parameters
+    /// - result: The food found by the search.
+    /// - score: How well the food matches.
+    pub fn new(result: &USDASearchResult, score: f32) -> Self {
+        MatchCandidate {
+            fdc_id: result.fdc_id,
+            description: result.description.to_string(),
+            data_type: result.data_type.clone(),
+            brand: result.brand().map(str::to_string),
+            score,
+        }
+    }
+}
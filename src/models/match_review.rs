@@ -0,0 +1,14 @@
+//! Holds the candidates of a food that could not be matched automatically.
+use serde::{Serialize, Deserialize};
+use crate::models::match_candidate::MatchCandidate;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchReview{
+    /// The id of the food.
+    pub food_id: i32,
+    /// The name of the food.
+    pub food_name: String,
+    /// The best candidates, best first. Empty if the search found nothing.
+    pub candidates: Vec<MatchCandidate>,
+}
@@ -0,0 +1,137 @@
+//! Holds when an existing property of a food is overwritten with the value from the FDC database.
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::{Serialize, Deserialize};
+
+/// The appsettings use the same syntax as the command line, so policies are (de)serialized as strings.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub enum MergePolicy{
+    /// Existing values are never overwritten, only missing properties are added.
+    Never,
+    /// Existing values always follow the FDC database. Properties the FDC food does not have are removed.
+    Always,
+    /// Existing values are only overwritten if they are empty or zero.
+    IfEmpty,
+    /// Existing values are only overwritten if they differ from the FDC value by more than the given fraction
+    /// of the FDC value (e.g. 0.05 for 5 %).
+    IfDiffers(f32),
+}
+
+impl MergePolicy{
+    /// Checks whether an existing value is overwritten.
+    /// ### Parameters
+    /// - old_amount: The amount the food has in Tandoor.
+    /// - new_amount: The amount from the FDC database.
+    /// ### Returns
+    /// True if the new amount replaces the old one. Missing FDC values never replace existing ones.
+    pub fn should_overwrite(&self, old_amount: Option<f32>, new_amount: Option<f32>) -> bool {
+        let Some(new_amount) = new_amount else {
+            return false;
+        };
+        match self {
+            MergePolicy::Never => false,
+            MergePolicy::Always => true,
+            MergePolicy::IfEmpty => old_amount.is_none_or(|old_amount| old_amount == 0.0),
+            MergePolicy::IfDiffers(tolerance) => old_amount.is_none_or(|old_amount| (old_amount - new_amount).abs() > tolerance * new_amount.abs()),
+        }
+    }
+}
+
+impl Display for MergePolicy{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergePolicy::Never => write!(f, "never"),
+            MergePolicy::Always => write!(f, "always"),
+            MergePolicy::IfEmpty => write!(f, "if-empty"),
+            MergePolicy::IfDiffers(tolerance) => write!(f, "if-differs:{}", tolerance),
+        }
+    }
+}
+
+impl TryFrom<String> for MergePolicy{
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<MergePolicy> for String{
+    fn from(value: MergePolicy) -> Self {
+        value.to_string()
+    }
+}
+
+impl FromStr for MergePolicy{
+    type Err = String;
+
+    /// Parses a policy as given on the command line or in the appsettings (never, always, if-empty or if-differs:<tolerance>).
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            None if value == "never" => Ok(MergePolicy::Never),
+            None if value == "always" => Ok(MergePolicy::Always),
+            None if value == "if-empty" => Ok(MergePolicy::IfEmpty),
+            Some(("if-differs", tolerance)) => tolerance
+                .parse::<f32>()
+                .ok()
+                .filter(|tolerance| *tolerance >= 0.0)
+                .map(MergePolicy::IfDiffers)
+                .ok_or(format!("\"{}\" is not a valid tolerance, use a fraction like 0.05.", tolerance)),
+            _ => Err(format!("\"{}\" is not a merge policy, use never, always, if-empty or if-differs:<tolerance>.", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_policies() {
+        assert_eq!("never".parse::<MergePolicy>(), Ok(MergePolicy::Never));
+        assert_eq!("always".parse::<MergePolicy>(), Ok(MergePolicy::Always));
+        assert_eq!("if-empty".parse::<MergePolicy>(), Ok(MergePolicy::IfEmpty));
+        assert_eq!("if-differs:0.05".parse::<MergePolicy>(), Ok(MergePolicy::IfDiffers(0.05)));
+    }
+
+    #[test]
+    fn rejects_invalid_policies() {
+        assert!("sometimes".parse::<MergePolicy>().is_err());
+        assert!("if-differs".parse::<MergePolicy>().is_err());
+        assert!("if-differs:-1".parse::<MergePolicy>().is_err());
+        assert!("if-differs:abc".parse::<MergePolicy>().is_err());
+    }
+
+    #[test]
+    fn deserializes_the_command_line_syntax() {
+        let policies: Vec<MergePolicy> = serde_json::from_str(r#"["never", "if-empty", "if-differs:0.1"]"#).unwrap();
+        assert_eq!(policies, vec![MergePolicy::Never, MergePolicy::IfEmpty, MergePolicy::IfDiffers(0.1)]);
+        assert_eq!(serde_json::to_string(&MergePolicy::IfDiffers(0.1)).unwrap(), r#""if-differs:0.1""#);
+    }
+
+    #[test]
+    fn never_keeps_present_values() {
+        assert!(!MergePolicy::Never.should_overwrite(Some(1.0), Some(2.0)));
+        assert!(MergePolicy::Always.should_overwrite(Some(1.0), Some(2.0)));
+    }
+
+    #[test]
+    fn missing_fdc_values_never_overwrite() {
+        assert!(!MergePolicy::Always.should_overwrite(Some(1.0), None));
+    }
+
+    #[test]
+    fn if_empty_only_overwrites_missing_or_zero_values() {
+        assert!(MergePolicy::IfEmpty.should_overwrite(None, Some(2.0)));
+        assert!(MergePolicy::IfEmpty.should_overwrite(Some(0.0), Some(2.0)));
+        assert!(!MergePolicy::IfEmpty.should_overwrite(Some(1.0), Some(2.0)));
+    }
+
+    #[test]
+    fn if_differs_respects_the_tolerance() {
+        let policy = MergePolicy::IfDiffers(0.05);
+        assert!(!policy.should_overwrite(Some(102.0), Some(100.0)));
+        assert!(policy.should_overwrite(Some(110.0), Some(100.0)));
+    }
+}
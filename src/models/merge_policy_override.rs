@@ -0,0 +1,29 @@
+//! Holds a merge policy given on the command line.
+use std::str::FromStr;
+use crate::models::merge_policy::MergePolicy;
+
+#[derive(Debug, Clone)]
+pub struct MergePolicyOverride{
+    /// The name of the property the policy applies to. The policy applies to all properties if not set.
+    pub property: Option<String>,
+    /// The policy.
+    pub policy: MergePolicy,
+}
+
+impl FromStr for MergePolicyOverride{
+    type Err = String;
+
+    /// Parses `<policy>` or `<property>=<policy>`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.rsplit_once('=') {
+            Some((property, policy)) => Ok(MergePolicyOverride {
+                property: Some(property.to_string()),
+                policy: policy.parse()?,
+            }),
+            None => Ok(MergePolicyOverride {
+                property: None,
+                policy: value.parse()?,
+            }),
+        }
+    }
+}
@@ -2,4 +2,13 @@
 pub mod tandoor;
 pub mod usda;
 pub mod configuration;
-pub mod command_line_arguments;
\ No newline at end of file
+pub mod command_line_arguments;
+pub mod food_diff;
+pub mod property_change;
+pub mod match_candidate;
+pub mod match_review;
+pub mod local_mapping_entry;
+pub mod food_mapping_entry;
+pub mod mapping_precedence;
+pub mod merge_policy;
+pub mod merge_policy_override;
\ No newline at end of file
@@ -0,0 +1,15 @@
+//! Represents the change of a single property of a food.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyChange{
+    /// The name of the property in Tandoor.
+    pub property: String,
+    /// The amount before the update. None if the food did not have the property.
+    pub old_amount: Option<f32>,
+    /// The amount after the update. None if the property is removed.
+    pub new_amount: Option<f32>,
+    /// The ID of the FDC nutrient the new amount is taken from.
+    pub fdc_nutrient_id: Option<i32>,
+}
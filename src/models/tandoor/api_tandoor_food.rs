@@ -1,6 +1,7 @@
 //! Represents a food item to be sent to the database.
 use serde::{Serialize, Deserialize};
 use crate::models::tandoor::api_tandoor_food_property::ApiTandoorFoodProperty;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
 use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,6 +16,15 @@ pub struct ApiTandoorFood {
     pub properties: Vec<ApiTandoorFoodProperty>,
     /// URL of the food in the FDC database.
     pub url: Option<String>,
+    /// The amount of the food the property values refer to (e.g. 100 for 100 g).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties_food_amount: Option<f32>,
+    /// The unit of the amount the property values refer to. Always sent, so restoring a food without unit removes the unit.
+    #[serde(default)]
+    pub properties_food_unit: Option<ApiTandoorUnit>,
+    /// When the food was last modified, if the Tandoor instance reports it. Never sent to Tandoor.
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<String>,
 }
 
 impl From<InternalTandoorFood> for ApiTandoorFood{
@@ -25,9 +35,12 @@ impl From<InternalTandoorFood> for ApiTandoorFood{
             fdc_id: value.fdc_id,
             properties: value.properties
                 .into_iter()
-                .map(|x| ApiTandoorFoodProperty::from(x))
+                .map(ApiTandoorFoodProperty::from)
                 .collect(),
             url: value.url,
+            properties_food_amount: value.properties_food_amount,
+            properties_food_unit: value.properties_food_unit,
+            updated_at: None,
         }
     }
 }
@@ -43,6 +56,9 @@ impl From<&InternalTandoorFood> for ApiTandoorFood{
                 .map(|x| ApiTandoorFoodProperty::from(x.clone()))
                 .collect(),
             url: value.url.clone(),
+            properties_food_amount: value.properties_food_amount,
+            properties_food_unit: value.properties_food_unit.clone(),
+            updated_at: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,10 @@
+//! Represents a reference to a food, as used by unit conversions.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiTandoorFoodReference {
+    /// The id of the food.
+    pub id: i32,
+    /// The name of the food.
+    pub name: String,
+}
@@ -1,6 +1,5 @@
 //! Holding an API response as given from the Tandoor API when requesting foods.
 use serde::{Serialize, Deserialize};
-use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiFoodResponse {
@@ -8,6 +7,7 @@ pub struct ApiFoodResponse {
     pub count: i32,
     /// The URL to call to get the next page of foods
     pub next: Option<String>,
-    /// List holding [ApiTandoorFood] representing the requested foods.
-    pub results: Vec<ApiTandoorFood>,
+    /// List holding the requested foods. They are kept raw and read one by one into [crate::models::tandoor::api_tandoor_food::ApiTandoorFood],
+    /// so a single malformed food does not fail the whole page.
+    pub results: Vec<serde_json::Value>,
 }
\ No newline at end of file
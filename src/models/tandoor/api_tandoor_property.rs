@@ -8,14 +8,19 @@ pub struct ApiTandoorProperty {
     pub name: String,
 
     /// The FDC ID of the property.
-    pub fdc_id: Option<i32>
+    pub fdc_id: Option<i32>,
+
+    /// The unit of the property (e.g. g or kcal). Never sent to Tandoor when updating a food.
+    #[serde(default, skip_serializing)]
+    pub unit: Option<String>,
 }
 
 impl From<InternalTandoorProperty> for ApiTandoorProperty{
     fn from(value: InternalTandoorProperty) -> Self {
         ApiTandoorProperty{
             name: value.name,
-            fdc_id: value.fdc_id.into()
+            fdc_id: value.fdc_id,
+            unit: value.unit,
         }
     }
 }
\ No newline at end of file
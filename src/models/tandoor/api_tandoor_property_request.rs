@@ -0,0 +1,14 @@
+//! Represents a property type to be created in the database.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiTandoorPropertyRequest {
+    /// The name of the property.
+    pub name: String,
+    /// The unit of the property (e.g. g or kcal).
+    pub unit: String,
+    /// The FDC ID of the nutrient the property is imported from.
+    pub fdc_id: i32,
+    /// The position of the property when the properties are listed.
+    pub order: i32,
+}
@@ -0,0 +1,29 @@
+//! Represents a unit (e.g. g or cup) of the Tandoor instance.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ApiTandoorUnit {
+    /// The id of the unit. Not set for units that were not created yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    /// The name of the unit.
+    pub name: String,
+    /// The plural of the name of the unit. Never sent to Tandoor.
+    #[serde(default, skip_serializing)]
+    pub plural_name: Option<String>,
+}
+
+impl ApiTandoorUnit {
+    /// Checks whether both units are the same unit, by their id if both have one and by their name otherwise.
+    pub fn is_same_unit(&self, other: &ApiTandoorUnit) -> bool {
+        match (self.id, other.id) {
+            (Some(id), Some(other_id)) => id == other_id,
+            _ => self.name.eq_ignore_ascii_case(&other.name),
+        }
+    }
+
+    /// Checks whether the unit is called by the given name, in singular or plural. Case is ignored.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) || self.plural_name.as_deref().is_some_and(|plural_name| plural_name.eq_ignore_ascii_case(name))
+    }
+}
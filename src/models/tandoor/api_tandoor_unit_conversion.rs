@@ -0,0 +1,22 @@
+//! Represents a conversion between two units of a food (e.g. 1 piece = 50 g).
+use serde::{Serialize, Deserialize};
+use crate::models::tandoor::api_tandoor_food_reference::ApiTandoorFoodReference;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiTandoorUnitConversion {
+    /// The id of the conversion. Not set for conversions that were not created yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    /// The amount of the base unit.
+    pub base_amount: f32,
+    /// The unit converted from.
+    pub base_unit: ApiTandoorUnit,
+    /// The amount of the converted unit equal to the amount of the base unit.
+    pub converted_amount: f32,
+    /// The unit converted to.
+    pub converted_unit: ApiTandoorUnit,
+    /// The food the conversion applies to. Conversions without a food apply to all foods.
+    #[serde(default)]
+    pub food: Option<ApiTandoorFoodReference>,
+}
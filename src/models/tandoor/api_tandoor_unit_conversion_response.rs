@@ -0,0 +1,13 @@
+//! Holding an API response as given from the Tandoor unit-conversion endpoint.
+use serde::{Serialize, Deserialize};
+use crate::models::tandoor::api_tandoor_unit_conversion::ApiTandoorUnitConversion;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiUnitConversionResponse {
+    /// The total number of conversions matching the request.
+    pub count: i32,
+    /// The URL to call to get the next page of conversions
+    pub next: Option<String>,
+    /// List holding [ApiTandoorUnitConversion] representing the requested conversions.
+    pub results: Vec<ApiTandoorUnitConversion>
+}
@@ -0,0 +1,13 @@
+//! Holding an API response as given from the Tandoor unit endpoint.
+use serde::{Serialize, Deserialize};
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiUnitResponse {
+    /// The total number of units matching the request.
+    pub count: i32,
+    /// The URL to call to get the next page of units
+    pub next: Option<String>,
+    /// List holding [ApiTandoorUnit] representing the requested units.
+    pub results: Vec<ApiTandoorUnit>
+}
@@ -1,6 +1,7 @@
 //! Holds a food item as it is returned from the database.
 use serde::{Serialize, Deserialize};
 use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
 use crate::models::tandoor::internal_tandoor_food_property::InternalTandoorFoodProperty;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -15,6 +16,12 @@ pub struct InternalTandoorFood {
     pub properties: Vec<InternalTandoorFoodProperty>,
     /// URL of the food in the FDC database.
     pub url: Option<String>,
+    /// The amount of the food the property values refer to (e.g. 100 for 100 g).
+    #[serde(default)]
+    pub properties_food_amount: Option<f32>,
+    /// The unit of the amount the property values refer to.
+    #[serde(default)]
+    pub properties_food_unit: Option<ApiTandoorUnit>,
 }
 
 impl TryFrom<ApiTandoorFood> for InternalTandoorFood {
@@ -26,10 +33,12 @@ impl TryFrom<ApiTandoorFood> for InternalTandoorFood {
             fdc_id: value.fdc_id,
             properties: value.properties
                 .into_iter()
-                .map(|api_food_property| InternalTandoorFoodProperty::try_from(api_food_property))
+                .map(InternalTandoorFoodProperty::try_from)
                 .filter_map(Result::ok)
                 .collect(),
             url: value.url,
+            properties_food_amount: value.properties_food_amount,
+            properties_food_unit: value.properties_food_unit,
         })
     }
 }
\ No newline at end of file
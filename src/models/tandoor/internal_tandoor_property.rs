@@ -7,7 +7,10 @@ pub struct InternalTandoorProperty {
     /// The name of the property.
     pub name: String,
     /// The FoodData Central ID of that property.
-    pub fdc_id: Option<i32>
+    pub fdc_id: Option<i32>,
+    /// The unit of the property (e.g. g or kcal).
+    #[serde(default)]
+    pub unit: Option<String>,
 }
 
 impl TryFrom<ApiTandoorProperty> for InternalTandoorProperty {
@@ -15,7 +18,8 @@ impl TryFrom<ApiTandoorProperty> for InternalTandoorProperty {
     fn try_from(value: ApiTandoorProperty) -> Result<Self, Self::Error> {        
         Ok(Self{
             name: value.name,
-            fdc_id: value.fdc_id
+            fdc_id: value.fdc_id,
+            unit: value.unit,
         })
     }
 }
\ No newline at end of file
@@ -7,5 +7,10 @@ pub mod internal_tandoor_property;
 pub mod api_tandoor_food;
 pub mod api_tandoor_property;
 pub mod api_tandoor_food_property;
-pub mod api_tandoor_endpoints;
-pub mod api_tandoor_property_response;
\ No newline at end of file
+pub mod api_tandoor_property_response;
+pub mod api_tandoor_unit;
+pub mod api_tandoor_unit_response;
+pub mod api_tandoor_unit_conversion;
+pub mod api_tandoor_unit_conversion_response;
+pub mod api_tandoor_food_reference;
+pub mod api_tandoor_property_request;
@@ -1,5 +1,13 @@
 //! Modules representing USDA related objects.
 pub mod usda_food;
 pub mod usda_nutrient;
-pub mod usda_api_response;
-pub mod usda_nutrient_information;
\ No newline at end of file
+pub mod usda_nutrient_information;
+pub mod usda_cache_entry;
+pub mod usda_foods_request;
+pub mod usda_data_type;
+pub mod usda_search_request;
+pub mod usda_search_response;
+pub mod usda_search_result;
+pub mod usda_search_nutrient;
+pub mod usda_food_portion;
+pub mod usda_measure_unit;
\ No newline at end of file
@@ -0,0 +1,14 @@
+//! Holds a FDC response as it is stored in the on-disk cache.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct USDACacheEntry{
+    /// Unix timestamp (in seconds) of when the food was fetched from the FDC database.
+    pub fetched_at: u64,
+    /// The IDs of the nutrients the response was limited to. None if all nutrients were requested.
+    #[serde(default)]
+    pub nutrient_ids: Option<Vec<i32>>,
+    /// The raw food as returned by the FDC database.
+    pub food: serde_json::Value,
+}
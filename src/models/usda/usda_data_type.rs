@@ -0,0 +1,28 @@
+//! Holds the data types of foods in the USDA FDC database.
+use serde::{Serialize, Deserialize};
+
+#[derive(clap::ValueEnum, Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum USDADataType{
+    /// Foods analyzed in detail by the USDA.
+    Foundation,
+    /// Foods of the former Standard Reference database.
+    #[serde(rename = "SR Legacy")]
+    SrLegacy,
+    /// Foods of the Food and Nutrient Database for Dietary Studies.
+    #[serde(rename = "Survey (FNDDS)")]
+    Survey,
+    /// Branded foods as reported by their manufacturers.
+    Branded,
+}
+
+impl USDADataType{
+    /// Gets the name of the data type used by the FDC API.
+    pub fn api_name(&self) -> &'static str {
+        match self {
+            USDADataType::Foundation => "Foundation",
+            USDADataType::SrLegacy => "SR Legacy",
+            USDADataType::Survey => "Survey (FNDDS)",
+            USDADataType::Branded => "Branded",
+        }
+    }
+}
@@ -1,12 +1,16 @@
 //! Holds a food item as requested from the USDA FDC database.
 use serde::{Serialize, Deserialize};
 
+use crate::models::usda::usda_food_portion::USDAFoodPortion;
 use crate::models::usda::usda_nutrient::USDANutrient;
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all(deserialize = "camelCase"))]
 pub struct USDAFood{
     /// The FDC ID of the food.
     pub fdc_id: i32,
     /// List of [USDANutrient]s associated with this food.
     pub food_nutrients: Vec<USDANutrient>,
+    /// List of [USDAFoodPortion]s of the food (e.g. 1 cup = 240 g). Branded foods do not have any.
+    #[serde(default)]
+    pub food_portions: Vec<USDAFoodPortion>,
 }
\ No newline at end of file
@@ -0,0 +1,92 @@
+//! Holds a portion of a food item (e.g. 1 cup = 240 g).
+use serde::{Serialize, Deserialize};
+use crate::models::usda::usda_measure_unit::USDAMeasureUnit;
+
+/// The name the FDC database uses for portions without a unit of their own.
+const UNDETERMINED_UNIT: &str = "undetermined";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct USDAFoodPortion{
+    /// How many units the portion has.
+    pub amount: Option<f32>,
+    /// How many grams the portion weighs.
+    pub gram_weight: Option<f32>,
+    /// Describes the unit if the portion has no measure unit (e.g. "large" or "cup, chopped").
+    pub modifier: Option<String>,
+    /// Describes the whole portion (e.g. "1 cup").
+    pub portion_description: Option<String>,
+    /// The unit of the portion.
+    pub measure_unit: Option<USDAMeasureUnit>,
+}
+
+impl USDAFoodPortion{
+    /// Gets the amount and the name of the unit the portion is measured in.
+    /// ### Returns
+    /// Tuple of the amount and the lowercase name of the unit or None if the portion has no readable unit.
+    /// ### Remarks
+    /// Depending on the data type the FDC database gives the unit in the measure unit, in the modifier
+    /// (for SR Legacy foods) or only in the description (for Survey foods) of the portion.
+    pub fn measure(&self) -> Option<(f32, String)> {
+        let amount = self.amount.unwrap_or(1.0);
+        if let Some(unit) = self.measure_unit.as_ref().map(|unit| unit.name.trim()).filter(|name| !name.is_empty() && *name != UNDETERMINED_UNIT) {
+            return Some((amount, unit.to_lowercase()));
+        }
+        if let Some(modifier) = self.modifier.as_deref().map(unit_name).filter(|name| !name.is_empty() && name.parse::<f32>().is_err()) {
+            return Some((amount, modifier));
+        }
+        let (amount, unit) = self.portion_description.as_deref()?.trim().split_once(' ')?;
+        let unit = unit_name(unit);
+        match (amount.parse::<f32>(), unit.is_empty()) {
+            (Ok(amount), false) => Some((amount, unit)),
+            _ => None,
+        }
+    }
+}
+
+/// Cuts the details off a unit description, e.g. "cup, chopped" becomes "cup".
+fn unit_name(description: &str) -> String {
+    description
+        .split([',', '('])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn portion(amount: Option<f32>, measure_unit: &str, modifier: Option<&str>, portion_description: Option<&str>) -> USDAFoodPortion {
+        USDAFoodPortion {
+            amount,
+            gram_weight: Some(100.0),
+            modifier: modifier.map(str::to_string),
+            portion_description: portion_description.map(str::to_string),
+            measure_unit: Some(USDAMeasureUnit { name: measure_unit.to_string() }),
+        }
+    }
+
+    #[test]
+    fn uses_the_measure_unit() {
+        assert_eq!(portion(Some(2.0), "Cup", None, None).measure(), Some((2.0, "cup".to_string())));
+    }
+
+    #[test]
+    fn uses_the_modifier_of_undetermined_units() {
+        assert_eq!(portion(Some(1.0), "undetermined", Some("cup, chopped"), None).measure(), Some((1.0, "cup".to_string())));
+    }
+
+    #[test]
+    fn uses_the_description_if_the_modifier_is_a_code() {
+        assert_eq!(portion(None, "undetermined", Some("10205"), Some("1 cup, NFS")).measure(), Some((1.0, "cup".to_string())));
+        assert_eq!(portion(None, "undetermined", None, Some("0.5 large (2 oz)")).measure(), Some((0.5, "large".to_string())));
+    }
+
+    #[test]
+    fn ignores_portions_without_readable_unit() {
+        assert_eq!(portion(None, "undetermined", None, Some("Quantity not specified")).measure(), None);
+        assert_eq!(portion(None, "undetermined", None, None).measure(), None);
+    }
+}
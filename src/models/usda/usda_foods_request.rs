@@ -0,0 +1,14 @@
+//! Holds a request for multiple foods sent to the USDA FDC database.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct USDAFoodsRequest{
+    /// The FDC IDs of the requested foods.
+    pub fdc_ids: Vec<i32>,
+    /// The format of the returned foods (abridged or full).
+    pub format: String,
+    /// The nutrient numbers to limit the returned nutrients to. All nutrients are returned if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nutrients: Option<Vec<u32>>,
+}
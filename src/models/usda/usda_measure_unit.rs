@@ -0,0 +1,9 @@
+//! Holds the unit a portion of a food is measured in.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct USDAMeasureUnit{
+    /// The name of the unit (e.g. cup). Portions without a unit of their own use "undetermined".
+    pub name: String,
+}
@@ -2,7 +2,7 @@
 use serde::{Serialize, Deserialize};
 use crate::models::usda::usda_nutrient_information::USDANutrientInformation;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct USDANutrient{
     /// How much of that nutrient is in the food.
     pub amount: Option<f32>,
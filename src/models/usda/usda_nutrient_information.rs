@@ -1,12 +1,14 @@
 //! Holds information about a nutrient.
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct USDANutrientInformation{
     /// The ID of the nutrient
     pub id: i32,
     /// The name of the nutrient.
     pub name: String,
-    
+    /// The unit the amount of the nutrient is given in (e.g. G, MG or KCAL).
+    #[serde(default)]
+    pub unit_name: Option<String>,
 }
\ No newline at end of file
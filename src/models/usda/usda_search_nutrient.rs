@@ -0,0 +1,11 @@
+//! Holds a nutrient of a food found by a search in the USDA FDC database.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct USDASearchNutrient{
+    /// The ID of the nutrient.
+    pub nutrient_id: i32,
+    /// How much of that nutrient is in the food.
+    pub value: Option<f32>,
+}
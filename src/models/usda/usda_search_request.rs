@@ -0,0 +1,16 @@
+//! Holds a search request sent to the USDA FDC database.
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct USDASearchRequest{
+    /// The search terms.
+    pub query: String,
+    /// The data types (e.g. Foundation or Branded) to limit the results to. All data types are searched if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<Vec<String>>,
+    /// How many results are returned per page.
+    pub page_size: u32,
+    /// The page to return, starting at 1.
+    pub page_number: u32,
+}
@@ -0,0 +1,16 @@
+//! Holds a page of search results returned by the USDA FDC database.
+use serde::{Serialize, Deserialize};
+use crate::models::usda::usda_search_result::USDASearchResult;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct USDASearchResponse{
+    /// The number of foods matching the search.
+    pub total_hits: i32,
+    /// The number of the returned page.
+    pub current_page: i32,
+    /// The number of pages available.
+    pub total_pages: i32,
+    /// The foods on this page.
+    pub foods: Vec<USDASearchResult>,
+}
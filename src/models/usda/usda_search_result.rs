@@ -0,0 +1,38 @@
+//! Holds a single food found by a search in the USDA FDC database.
+use serde::{Serialize, Deserialize};
+use crate::models::usda::usda_search_nutrient::USDASearchNutrient;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct USDASearchResult{
+    /// The FDC ID of the food.
+    pub fdc_id: i32,
+    /// The description of the food.
+    pub description: String,
+    /// The data type of the food (e.g. Foundation or Branded).
+    pub data_type: Option<String>,
+    /// The company owning the brand of branded foods.
+    pub brand_owner: Option<String>,
+    /// The brand of branded foods.
+    pub brand_name: Option<String>,
+    /// The nutrients of the food per 100 g.
+    #[serde(default)]
+    pub food_nutrients: Vec<USDASearchNutrient>,
+}
+
+impl USDASearchResult{
+    /// Gets the brand of the food, preferring the brand name over the owner of the brand.
+    pub fn brand(&self) -> Option<&str> {
+        self.brand_name.as_deref().or(self.brand_owner.as_deref())
+    }
+
+    /// Gets the amount of the first of the given nutrients the food has.
+    /// ### Parameters
+    /// - nutrient_ids: The IDs of the nutrients in order of preference.
+    pub fn nutrient_amount(&self, nutrient_ids: &[i32]) -> Option<f32> {
+        nutrient_ids.iter().find_map(|id| self.food_nutrients
+            .iter()
+            .find(|n| n.nutrient_id == *id)
+            .and_then(|n| n.value))
+    }
+}
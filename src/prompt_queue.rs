@@ -0,0 +1,61 @@
+//! Serializes the questions asked to the user in interactive mode.
+//!
+//! Foods are processed concurrently, but the user can only answer one question at a time. Tasks needing input
+//! send their food to a single prompt task which asks about the foods one after another, so prompts never
+//! interleave and every answer belongs to the food it was given for. Reading from stdin happens on a blocking
+//! thread, so the runtime workers keep processing the other foods in the meantime.
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{mpsc, oneshot};
+use crate::candidate_picker::{CandidatePicker, PickResult};
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+
+/// A food waiting for the user.
+struct PromptRequest {
+    food: InternalTandoorFood,
+    respond_to: oneshot::Sender<PickResult>,
+}
+
+/// Queue of foods the user is asked about one at a time.
+pub struct PromptQueue {
+    sender: mpsc::UnboundedSender<PromptRequest>,
+    waiting: Arc<AtomicUsize>,
+}
+
+impl PromptQueue {
+    /// Creates the queue and starts the task asking the user.
+    /// ### Parameters
+    /// - candidate_picker: The picker used to ask the user.
+    pub fn new(candidate_picker: CandidatePicker) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let waiting = Arc::new(AtomicUsize::new(0));
+        tokio::spawn(run(candidate_picker, receiver, Arc::clone(&waiting)));
+        PromptQueue { sender, waiting }
+    }
+
+    /// Asks the user about the food as soon as all foods queued before were answered.
+    /// ### Parameters
+    /// - food: The food for which the FDC ID should be retrieved.
+    /// ### Returns
+    /// The decision of the user. The food is skipped if the prompt task stopped.
+    pub async fn ask(&self, food: &InternalTandoorFood) -> PickResult {
+        let (respond_to, response) = oneshot::channel();
+        self.waiting.fetch_add(1, Ordering::SeqCst);
+        if self.sender.send(PromptRequest { food: food.clone(), respond_to }).is_err() {
+            return PickResult::Skipped;
+        }
+        response.await.unwrap_or(PickResult::Skipped)
+    }
+}
+
+/// Asks about the queued foods one after another until all [PromptQueue]s are dropped.
+async fn run(candidate_picker: CandidatePicker, mut receiver: mpsc::UnboundedReceiver<PromptRequest>, waiting: Arc<AtomicUsize>) {
+    while let Some(request) = receiver.recv().await {
+        let still_waiting = waiting.fetch_sub(1, Ordering::SeqCst) - 1;
+        if still_waiting > 0 {
+            println!("\n{} more foods are waiting for your input.", still_waiting);
+        }
+        let result = candidate_picker.pick(&request.food).await;
+        let _ = request.respond_to.send(result);
+    }
+}
@@ -0,0 +1,95 @@
+//! Built-in sets of property types that can be created in Tandoor by the init-properties command.
+//!
+//! Every property is linked to the FDC nutrient it is imported from and uses the unit the FDC database gives the
+//! nutrient in. The properties are listed in the order they are shown in Tandoor.
+use crate::models::command_line_arguments::{Language, PropertyPreset};
+
+/// A property type of a preset.
+pub struct PresetProperty {
+    /// The ID of the FDC nutrient the property is imported from.
+    pub fdc_id: i32,
+    /// The unit of the property.
+    pub unit: &'static str,
+    /// The English name of the property.
+    pub name_en: &'static str,
+    /// The German name of the property.
+    pub name_de: &'static str,
+    /// The French name of the property.
+    pub name_fr: &'static str,
+}
+
+impl PresetProperty {
+    /// Gets the name of the property in the given language.
+    pub fn name(&self, language: Language) -> &'static str {
+        match language {
+            Language::En => self.name_en,
+            Language::De => self.name_de,
+            Language::Fr => self.name_fr,
+        }
+    }
+}
+
+/// Energy and the macronutrients most recipes are planned with.
+const BASIC_MACROS: &[PresetProperty] = &[
+    PresetProperty { fdc_id: 1008, unit: "kcal", name_en: "Energy", name_de: "Energie", name_fr: "Énergie" },
+    PresetProperty { fdc_id: 1004, unit: "g", name_en: "Fat", name_de: "Fett", name_fr: "Matières grasses" },
+    PresetProperty { fdc_id: 1005, unit: "g", name_en: "Carbohydrates", name_de: "Kohlenhydrate", name_fr: "Glucides" },
+    PresetProperty { fdc_id: 2000, unit: "g", name_en: "Sugars", name_de: "Zucker", name_fr: "Sucres" },
+    PresetProperty { fdc_id: 1079, unit: "g", name_en: "Fiber", name_de: "Ballaststoffe", name_fr: "Fibres alimentaires" },
+    PresetProperty { fdc_id: 1003, unit: "g", name_en: "Protein", name_de: "Eiweiß", name_fr: "Protéines" },
+];
+
+/// The nutrition declaration of the EU food information regulation (EU 1169/2011).
+/// The FDC database has no salt, so sodium is imported instead (salt = sodium × 2.5).
+const EU_LABEL: &[PresetProperty] = &[
+    PresetProperty { fdc_id: 1062, unit: "kJ", name_en: "Energy (kJ)", name_de: "Brennwert (kJ)", name_fr: "Énergie (kJ)" },
+    PresetProperty { fdc_id: 1008, unit: "kcal", name_en: "Energy (kcal)", name_de: "Brennwert (kcal)", name_fr: "Énergie (kcal)" },
+    PresetProperty { fdc_id: 1004, unit: "g", name_en: "Fat", name_de: "Fett", name_fr: "Matières grasses" },
+    PresetProperty { fdc_id: 1258, unit: "g", name_en: "Saturates", name_de: "Gesättigte Fettsäuren", name_fr: "Acides gras saturés" },
+    PresetProperty { fdc_id: 1005, unit: "g", name_en: "Carbohydrate", name_de: "Kohlenhydrate", name_fr: "Glucides" },
+    PresetProperty { fdc_id: 2000, unit: "g", name_en: "Sugars", name_de: "Zucker", name_fr: "Sucres" },
+    PresetProperty { fdc_id: 1079, unit: "g", name_en: "Fibre", name_de: "Ballaststoffe", name_fr: "Fibres alimentaires" },
+    PresetProperty { fdc_id: 1003, unit: "g", name_en: "Protein", name_de: "Eiweiß", name_fr: "Protéines" },
+    PresetProperty { fdc_id: 1093, unit: "mg", name_en: "Sodium", name_de: "Natrium", name_fr: "Sodium" },
+];
+
+/// Fatty acids, cholesterol, minerals and vitamins, added to the EU label by the full micronutrients preset.
+const MICRONUTRIENTS: &[PresetProperty] = &[
+    PresetProperty { fdc_id: 1292, unit: "g", name_en: "Monounsaturated fat", name_de: "Einfach ungesättigte Fettsäuren", name_fr: "Acides gras mono-insaturés" },
+    PresetProperty { fdc_id: 1293, unit: "g", name_en: "Polyunsaturated fat", name_de: "Mehrfach ungesättigte Fettsäuren", name_fr: "Acides gras polyinsaturés" },
+    PresetProperty { fdc_id: 1257, unit: "g", name_en: "Trans fat", name_de: "Transfettsäuren", name_fr: "Acides gras trans" },
+    PresetProperty { fdc_id: 1253, unit: "mg", name_en: "Cholesterol", name_de: "Cholesterin", name_fr: "Cholestérol" },
+    PresetProperty { fdc_id: 1087, unit: "mg", name_en: "Calcium", name_de: "Calcium", name_fr: "Calcium" },
+    PresetProperty { fdc_id: 1089, unit: "mg", name_en: "Iron", name_de: "Eisen", name_fr: "Fer" },
+    PresetProperty { fdc_id: 1090, unit: "mg", name_en: "Magnesium", name_de: "Magnesium", name_fr: "Magnésium" },
+    PresetProperty { fdc_id: 1091, unit: "mg", name_en: "Phosphorus", name_de: "Phosphor", name_fr: "Phosphore" },
+    PresetProperty { fdc_id: 1092, unit: "mg", name_en: "Potassium", name_de: "Kalium", name_fr: "Potassium" },
+    PresetProperty { fdc_id: 1095, unit: "mg", name_en: "Zinc", name_de: "Zink", name_fr: "Zinc" },
+    PresetProperty { fdc_id: 1098, unit: "mg", name_en: "Copper", name_de: "Kupfer", name_fr: "Cuivre" },
+    PresetProperty { fdc_id: 1101, unit: "mg", name_en: "Manganese", name_de: "Mangan", name_fr: "Manganèse" },
+    PresetProperty { fdc_id: 1103, unit: "µg", name_en: "Selenium", name_de: "Selen", name_fr: "Sélénium" },
+    PresetProperty { fdc_id: 1100, unit: "µg", name_en: "Iodine", name_de: "Jod", name_fr: "Iode" },
+    PresetProperty { fdc_id: 1106, unit: "µg", name_en: "Vitamin A", name_de: "Vitamin A", name_fr: "Vitamine A" },
+    PresetProperty { fdc_id: 1114, unit: "µg", name_en: "Vitamin D", name_de: "Vitamin D", name_fr: "Vitamine D" },
+    PresetProperty { fdc_id: 1109, unit: "mg", name_en: "Vitamin E", name_de: "Vitamin E", name_fr: "Vitamine E" },
+    PresetProperty { fdc_id: 1185, unit: "µg", name_en: "Vitamin K", name_de: "Vitamin K", name_fr: "Vitamine K" },
+    PresetProperty { fdc_id: 1162, unit: "mg", name_en: "Vitamin C", name_de: "Vitamin C", name_fr: "Vitamine C" },
+    PresetProperty { fdc_id: 1165, unit: "mg", name_en: "Thiamin (B1)", name_de: "Thiamin (B1)", name_fr: "Thiamine (B1)" },
+    PresetProperty { fdc_id: 1166, unit: "mg", name_en: "Riboflavin (B2)", name_de: "Riboflavin (B2)", name_fr: "Riboflavine (B2)" },
+    PresetProperty { fdc_id: 1167, unit: "mg", name_en: "Niacin (B3)", name_de: "Niacin (B3)", name_fr: "Niacine (B3)" },
+    PresetProperty { fdc_id: 1170, unit: "mg", name_en: "Pantothenic acid (B5)", name_de: "Pantothensäure (B5)", name_fr: "Acide pantothénique (B5)" },
+    PresetProperty { fdc_id: 1175, unit: "mg", name_en: "Vitamin B6", name_de: "Vitamin B6", name_fr: "Vitamine B6" },
+    PresetProperty { fdc_id: 1176, unit: "µg", name_en: "Biotin (B7)", name_de: "Biotin (B7)", name_fr: "Biotine (B7)" },
+    PresetProperty { fdc_id: 1190, unit: "µg", name_en: "Folate (B9)", name_de: "Folat (B9)", name_fr: "Folate (B9)" },
+    PresetProperty { fdc_id: 1178, unit: "µg", name_en: "Vitamin B12", name_de: "Vitamin B12", name_fr: "Vitamine B12" },
+    PresetProperty { fdc_id: 1180, unit: "mg", name_en: "Choline", name_de: "Cholin", name_fr: "Choline" },
+];
+
+/// Gets the properties of a preset in the order they are shown in Tandoor.
+pub fn properties_of(preset: PropertyPreset) -> Vec<&'static PresetProperty> {
+    match preset {
+        PropertyPreset::BasicMacros => BASIC_MACROS.iter().collect(),
+        PropertyPreset::EuLabel => EU_LABEL.iter().collect(),
+        PropertyPreset::FullMicronutrients => EU_LABEL.iter().chain(MICRONUTRIENTS).collect(),
+    }
+}
@@ -0,0 +1,181 @@
+//! Token bucket shared by all requests sent to a rate-limited API.
+//!
+//! The bucket is refilled continuously based on the limit per hour and corrected by the rate limit headers
+//! returned with every response. When the API answers with 429 all requests are paused until it may be called again.
+use std::sync::Mutex;
+use std::time::Duration;
+use log::{debug, info};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use tokio::time::Instant;
+
+/// How long to pause if the API rate-limited us without telling for how long.
+const DEFAULT_PAUSE: Duration = Duration::from_secs(60);
+
+/// The longest pause, as the limits are given per hour.
+const MAX_PAUSE: Duration = Duration::from_secs(3600);
+
+/// Token bucket limiting the requests sent to an API.
+pub struct RateLimiter {
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    capacity: f64,
+    tokens: f64,
+    refill_per_second: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl BucketState {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+    }
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter.
+    /// ### Parameters
+    /// - requests_per_hour: The number of requests allowed per hour until the API tells otherwise.
+    pub fn new(requests_per_hour: u32) -> Self {
+        let capacity = f64::from(requests_per_hour.max(1));
+        RateLimiter {
+            state: Mutex::new(BucketState {
+                capacity,
+                tokens: capacity,
+                refill_per_second: capacity / 3600.0,
+                last_refill: Instant::now(),
+                paused_until: None,
+            }),
+        }
+    }
+
+    /// Waits until a request may be sent and takes a token from the bucket.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                state.refill(now);
+                match state.paused_until {
+                    Some(paused_until) if paused_until > now => paused_until - now,
+                    _ if state.tokens >= 1.0 => {
+                        state.tokens -= 1.0;
+                        return;
+                    }
+                    _ => Duration::from_secs_f64((1.0 - state.tokens) / state.refill_per_second),
+                }
+            };
+            debug!("Waiting {:.1} seconds before sending the next request to avoid being rate-limited.", wait.as_secs_f64());
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Updates the bucket from the rate limit headers of a response.
+    /// ### Parameters
+    /// - headers: The headers of the response. `X-RateLimit-Limit` and `X-RateLimit-Remaining` are used if present.
+    pub fn update_from_headers(&self, headers: &HeaderMap) {
+        let limit = header_value(headers, "X-RateLimit-Limit");
+        let remaining = header_value(headers, "X-RateLimit-Remaining");
+
+        let mut state = self.state.lock().unwrap();
+        state.refill(Instant::now());
+        if let Some(limit) = limit.filter(|l| *l > 0.0) {
+            state.capacity = limit;
+            state.refill_per_second = limit / 3600.0;
+        }
+        if let Some(remaining) = remaining {
+            // Other requests may have been sent since, so never add tokens based on the header.
+            state.tokens = state.tokens.min(remaining);
+        }
+    }
+
+    /// Pauses all requests after the API rate-limited us.
+    /// ### Parameters
+    /// - headers: The headers of the response. `Retry-After` is used to determine the pause if present.
+    /// ### Returns
+    /// The duration of the pause, at most [MAX_PAUSE].
+    pub fn pause(&self, headers: &HeaderMap) -> Duration {
+        let pause = header_value(headers, RETRY_AFTER.as_str())
+            .map(|seconds| Duration::from_secs_f64(seconds.min(MAX_PAUSE.as_secs_f64())))
+            .unwrap_or(DEFAULT_PAUSE);
+
+        let mut state = self.state.lock().unwrap();
+        let paused_until = Instant::now() + pause;
+        state.tokens = 0.0;
+        state.paused_until = Some(state.paused_until.map_or(paused_until, |p| p.max(paused_until)));
+        info!("Rate-limited by the API. Pausing all requests for {} seconds.", pause.as_secs());
+        pause
+    }
+}
+
+/// Reads a numeric header value, ignoring missing or malformed headers.
+fn header_value(headers: &HeaderMap, name: &str) -> Option<f64> {
+    headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(values: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in values {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn tokens(rate_limiter: &RateLimiter) -> f64 {
+        rate_limiter.state.lock().unwrap().tokens
+    }
+
+    #[tokio::test]
+    async fn acquiring_takes_tokens_from_the_bucket() {
+        let rate_limiter = RateLimiter::new(3);
+        rate_limiter.acquire().await;
+        rate_limiter.acquire().await;
+        assert!((tokens(&rate_limiter) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn headers_lower_the_tokens_but_never_add_any() {
+        let rate_limiter = RateLimiter::new(1000);
+        rate_limiter.update_from_headers(&headers(&[("X-RateLimit-Limit", "3600"), ("X-RateLimit-Remaining", "5")]));
+        assert!(tokens(&rate_limiter) <= 5.01);
+        assert_eq!(rate_limiter.state.lock().unwrap().capacity, 3600.0);
+
+        rate_limiter.update_from_headers(&headers(&[("X-RateLimit-Remaining", "500")]));
+        assert!(tokens(&rate_limiter) < 6.0);
+    }
+
+    #[test]
+    fn malformed_headers_are_ignored() {
+        let rate_limiter = RateLimiter::new(10);
+        rate_limiter.update_from_headers(&headers(&[("X-RateLimit-Limit", "-1"), ("X-RateLimit-Remaining", "many")]));
+        assert_eq!(rate_limiter.state.lock().unwrap().capacity, 10.0);
+        assert!(tokens(&rate_limiter) > 9.99);
+    }
+
+    #[test]
+    fn pauses_for_the_retry_after_header() {
+        let rate_limiter = RateLimiter::new(10);
+        assert_eq!(rate_limiter.pause(&headers(&[("Retry-After", "30")])), Duration::from_secs(30));
+        let state = rate_limiter.state.lock().unwrap();
+        assert_eq!(state.tokens, 0.0);
+        assert!(state.paused_until.is_some_and(|paused_until| paused_until > Instant::now()));
+    }
+
+    #[test]
+    fn pauses_for_the_default_without_retry_after_header() {
+        assert_eq!(RateLimiter::new(10).pause(&HeaderMap::new()), DEFAULT_PAUSE);
+    }
+
+    #[test]
+    fn huge_retry_after_headers_are_capped() {
+        assert_eq!(RateLimiter::new(10).pause(&headers(&[("Retry-After", "1e30")])), MAX_PAUSE);
+    }
+}
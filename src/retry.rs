@@ -0,0 +1,133 @@
+//! Retries requests that failed due to transient errors.
+//!
+//! Requests are retried with an exponential backoff and jitter. If the server tells us when to retry using the
+//! `Retry-After` header, that time is used instead (up to the maximum delay). POST requests are not idempotent and are
+//! only retried if the caller knows that sending them twice does no harm.
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use log::{debug, warn};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+
+/// Policy describing how often and when failed requests are retried.
+pub struct RetryPolicy {
+    name: String,
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    retryable_statuses: Vec<StatusCode>,
+    retries: AtomicUsize,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy.
+    /// ### Parameters
+    /// - name: The name of the API the policy is used for (used for logging).
+    /// - max_retries: How often a request is retried before giving up.
+    /// - base_delay: The delay before the first retry. The delay doubles with every retry.
+    /// - max_delay: The maximum delay between two attempts.
+    pub fn new(name: &str, max_retries: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        RetryPolicy {
+            name: name.to_string(),
+            max_retries,
+            base_delay,
+            max_delay,
+            retryable_statuses: vec![
+                StatusCode::REQUEST_TIMEOUT,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retries: AtomicUsize::new(0),
+        }
+    }
+
+    /// Stops retrying responses with the given status (e.g. because the caller handles it itself).
+    pub fn without_status(mut self, status: StatusCode) -> Self {
+        self.retryable_statuses.retain(|s| *s != status);
+        self
+    }
+
+    /// The total number of retries done using this policy.
+    pub fn retries(&self) -> usize {
+        self.retries.load(Ordering::SeqCst)
+    }
+
+    /// Sends the request and retries it if it failed due to a transient error.
+    /// ### Parameters
+    /// - request: The request to send.
+    /// ### Returns
+    /// The last response (which might still have a non-successful status) or the last error.
+    /// ### Remarks
+    /// POST requests are sent only once, as Tandoor might have applied a request that failed (e.g. with a 502 from
+    /// a proxy) and sending it again would create a duplicate. Use [RetryPolicy::send_idempotent] for POST requests
+    /// that only read data.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let is_post = request.try_clone()
+            .and_then(|r| r.build().ok())
+            .is_some_and(|r| r.method() == Method::POST);
+        if is_post {
+            debug!("Not retrying {} POST request as it is not idempotent.", self.name);
+            return request.send().await;
+        }
+        self.send_idempotent(request).await
+    }
+
+    /// Sends the request and retries it if it failed due to a transient error, whatever its method is.
+    /// ### Parameters
+    /// - request: The request to send. Must be idempotent, e.g. a POST that only queries data.
+    /// ### Returns
+    /// The last response (which might still have a non-successful status) or the last error.
+    pub async fn send_idempotent(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let mut attempt: u32 = 0;
+        loop {
+            // Requests with streaming bodies cannot be cloned and are therefore only sent once.
+            let Some(current_request) = request.try_clone() else {
+                return request.send().await;
+            };
+            let can_retry = attempt < self.max_retries;
+
+            let delay = match current_request.send().await {
+                Ok(response) if can_retry && self.retryable_statuses.contains(&response.status()) => {
+                    warn!("{} request to {} failed with status {}, retrying.", self.name, response.url(), response.status());
+                    retry_after(&response).map_or_else(|| self.backoff(attempt), |delay| delay.min(self.max_delay))
+                }
+                Err(e) if can_retry && is_transient(&e) => {
+                    warn!("{} request failed: {}, retrying.", self.name, e);
+                    self.backoff(attempt)
+                }
+                result => return result,
+            };
+
+            attempt += 1;
+            self.retries.fetch_add(1, Ordering::SeqCst);
+            debug!("Waiting {:.1} seconds before retry {} of {}.", delay.as_secs_f64(), attempt, self.max_retries);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Calculates the delay before the next attempt using an exponential backoff with jitter.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        // Use half of the delay fixed and randomize the other half so parallel requests do not retry in lockstep.
+        let jitter = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        delay.mul_f64(0.5 + jitter * 0.5)
+    }
+}
+
+/// Checks whether the error was caused by the connection and might succeed when trying again.
+fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request()
+}
+
+/// Reads the delay given by the `Retry-After` header (in seconds).
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds: u64 = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(Duration::from_secs(seconds))
+}
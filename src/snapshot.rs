@@ -0,0 +1,69 @@
+//! Snapshots of foods taken before they are updated, so an update can be undone.
+//!
+//! A snapshot is a JSON Lines file holding one [InternalTandoorFood] per line. Foods are appended right before
+//! they are updated, so the snapshot is complete even if the program stops in the middle of a run.
+use std::error::Error;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use chrono::Utc;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+
+/// Writes the original state of foods to a snapshot file.
+pub struct SnapshotWriter {
+    path: PathBuf,
+    file: Mutex<Option<File>>,
+}
+
+impl SnapshotWriter {
+    /// Creates a writer for a new snapshot in the given directory. The file is created when the first food is saved.
+    /// ### Parameters
+    /// - directory: The directory the snapshot is stored in.
+    pub fn new(directory: &Path) -> Self {
+        let file_name = format!("snapshot-{}.jsonl", Utc::now().format("%Y%m%d-%H%M%S"));
+        SnapshotWriter {
+            path: directory.join(file_name),
+            file: Mutex::new(None),
+        }
+    }
+
+    /// The path of the snapshot file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends the food to the snapshot.
+    /// ### Parameters
+    /// - food: The food as it currently is in Tandoor.
+    pub fn save(&self, food: &InternalTandoorFood) -> Result<(), Box<dyn Error>> {
+        let line = serde_json::to_string(food)?;
+        let mut file = self.file.lock().unwrap();
+        if file.is_none() {
+            if let Some(directory) = self.path.parent() {
+                fs::create_dir_all(directory)?;
+            }
+            *file = Some(OpenOptions::new().create(true).append(true).open(&self.path)?);
+        }
+        if let Some(file) = file.as_mut() {
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads all foods from a snapshot file.
+/// ### Parameters
+/// - path: The path of the snapshot file.
+/// ### Returns
+/// The foods in the order they were saved or an error if the file could not be read.
+pub fn read_snapshot(path: &Path) -> Result<Vec<InternalTandoorFood>, Box<dyn Error>> {
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| serde_json::from_str(line).map_err(|e| format!("Line {} of the snapshot is not a valid food: {}", index + 1, e).into()))
+        .collect()
+}
@@ -0,0 +1,601 @@
+//! Client used to interact with a Tandoor instance independent of the API version it runs.
+//!
+//! The [TandoorClient] owns authentication, pagination and the endpoint layout while the differences between the
+//! API versions are described by implementations of [TandoorApiVersion]. Supporting a new version means adding an
+//! implementation and registering it in [supported_versions].
+pub mod v1;
+pub mod v2;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use chrono::{DateTime, NaiveDate, Utc};
+use log::{debug, trace, warn};
+use reqwest::{Client, Url};
+use serde::de::DeserializeOwned;
+use tokio::sync::Semaphore;
+use crate::retry::RetryPolicy;
+use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
+use crate::models::tandoor::api_tandoor_property::ApiTandoorProperty;
+use crate::models::tandoor::api_tandoor_property_request::ApiTandoorPropertyRequest;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+use crate::models::tandoor::api_tandoor_unit_conversion::ApiTandoorUnitConversion;
+use crate::models::tandoor::internal_tandoor_food::InternalTandoorFood;
+use crate::models::tandoor::internal_tandoor_property::InternalTandoorProperty;
+
+/// A single page returned by a list endpoint of the Tandoor API.
+pub struct TandoorPage<T> {
+    /// The total number of elements if the endpoint reports it.
+    pub count: Option<i32>,
+    /// The URL to call to get the next page.
+    pub next: Option<String>,
+    /// The elements on this page.
+    pub results: Vec<T>,
+}
+
+/// An element of a list endpoint that could not be read.
+#[derive(Debug)]
+pub struct SkippedElement {
+    /// The ID of the element if it could be read.
+    pub id: Option<i32>,
+    /// The name of the element if it could be read.
+    pub name: Option<String>,
+    /// Why the element could not be read.
+    pub reason: String,
+}
+
+impl Display for SkippedElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.name, self.id) {
+            (Some(name), Some(id)) => write!(f, "{} (ID {})", name, id),
+            (Some(name), None) => write!(f, "{}", name),
+            (None, Some(id)) => write!(f, "ID {}", id),
+            (None, None) => write!(f, "<unknown>"),
+        }
+    }
+}
+
+/// All foods loaded from Tandoor.
+pub struct LoadedFoods {
+    /// The foods that were read successfully.
+    pub foods: Vec<InternalTandoorFood>,
+    /// The foods that could not be read.
+    pub skipped: Vec<SkippedElement>,
+    /// The number of foods Tandoor reported (if any).
+    pub expected: Option<i32>,
+}
+
+/// Describes the parts of the Tandoor API that differ between versions.
+pub trait TandoorApiVersion: Send + Sync {
+    /// The name of the version (e.g. v2).
+    fn name(&self) -> &'static str;
+
+    /// The path of the food endpoint relative to the API root.
+    fn food_endpoint(&self) -> &'static str;
+
+    /// The path of the property type endpoint relative to the API root.
+    fn property_type_endpoint(&self) -> &'static str;
+
+    /// The path of the unit endpoint relative to the API root.
+    fn unit_endpoint(&self) -> &'static str;
+
+    /// The path of the unit conversion endpoint relative to the API root.
+    fn unit_conversion_endpoint(&self) -> &'static str;
+
+    /// Parses a response of the property type endpoint.
+    fn parse_property_types(&self, body: &str) -> Result<TandoorPage<ApiTandoorProperty>, Box<dyn Error>>;
+
+    /// Parses a response of the food endpoint.
+    /// Foods that cannot be read are returned as [SkippedElement] instead of failing the whole page.
+    fn parse_foods(&self, body: &str) -> Result<TandoorPage<Result<ApiTandoorFood, SkippedElement>>, Box<dyn Error>>;
+
+    /// Parses a response of the unit endpoint.
+    fn parse_units(&self, body: &str) -> Result<TandoorPage<ApiTandoorUnit>, Box<dyn Error>>;
+
+    /// Parses a response of the unit conversion endpoint.
+    fn parse_unit_conversions(&self, body: &str) -> Result<TandoorPage<ApiTandoorUnitConversion>, Box<dyn Error>>;
+
+    /// Builds the body sent to the food endpoint to update a food.
+    fn serialize_food(&self, food: &ApiTandoorFood) -> Result<serde_json::Value, Box<dyn Error>> {
+        Ok(serde_json::to_value(food)?)
+    }
+}
+
+/// Reads the elements of a page one by one, so a single malformed element does not fail the whole page.
+pub fn parse_elements<T: DeserializeOwned>(values: Vec<serde_json::Value>) -> Vec<Result<T, SkippedElement>> {
+    values
+        .into_iter()
+        .map(|value| {
+            let id = value.get("id").and_then(|id| id.as_i64()).and_then(|id| i32::try_from(id).ok());
+            let name = value.get("name").and_then(|name| name.as_str()).map(str::to_string);
+            serde_json::from_value(value).map_err(|e| SkippedElement {
+                id,
+                name,
+                reason: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// All supported API versions, newest first.
+pub fn supported_versions() -> Vec<Box<dyn TandoorApiVersion>> {
+    vec![Box::new(v2::TandoorV2), Box::new(v1::TandoorV1)]
+}
+
+/// Client for the Tandoor API.
+pub struct TandoorClient {
+    client: Client,
+    api_key: String,
+    api_base_url: String,
+    version: Box<dyn TandoorApiVersion>,
+    write_slots: Semaphore,
+    retry_policy: Arc<RetryPolicy>,
+}
+
+impl TandoorClient {
+    /// Creates a client for the Tandoor instance at the given URL.
+    /// ### Parameters
+    /// - client: The client used for any http requests
+    /// - tandoor_url: The URL of the Tandoor instance as configured by the user.
+    /// - api_key: The API key to interact with the Tandoor API
+    /// - version: The API version to use. If None the version is detected.
+    /// - max_concurrent_writes: How many updates may be sent to Tandoor at the same time.
+    /// - retry_policy: The policy used to retry requests that failed due to transient errors.
+    /// ### Returns
+    /// The client or an error if the version is not supported or could not be detected.
+    pub async fn connect(client: Client, tandoor_url: &str, api_key: &str, version: Option<&str>, max_concurrent_writes: usize, retry_policy: Arc<RetryPolicy>) -> Result<Self, Box<dyn Error>> {
+        let api_base_url = api_base_url(tandoor_url);
+        let version = match version {
+            Some(name) => {
+                debug!("Using configured Tandoor API version {}", name);
+                supported_versions()
+                    .into_iter()
+                    .find(|v| v.name() == name)
+                    .ok_or_else(|| format!("The given API version is not supported: {}", name))?
+            }
+            None => detect_version(&client, &api_base_url, api_key).await?,
+        };
+
+        Ok(TandoorClient {
+            client,
+            api_key: api_key.to_string(),
+            api_base_url,
+            version,
+            write_slots: Semaphore::new(max_concurrent_writes.max(1)),
+            retry_policy,
+        })
+    }
+
+    /// The name of the API version used.
+    pub fn version(&self) -> &str {
+        self.version.name()
+    }
+
+    /// The URL of the API root.
+    pub fn base_url(&self) -> &str {
+        &self.api_base_url
+    }
+
+    /// Gets all food properties of the Tandoor instance
+    /// ### Returns
+    /// Vec containing a list of all properties that were returned by the Tandoor API, including the ones without an FDC ID.
+    pub async fn get_properties(&self) -> Result<Vec<InternalTandoorProperty>, Box<dyn Error>> {
+        let url = format!("{}{}", self.api_base_url, self.version.property_type_endpoint());
+        let (properties, expected_property_number) = self
+            .get_all_pages(url, |body| self.version.parse_property_types(body))
+            .await?;
+
+        if let Some(expected_property_number) = expected_property_number {
+            if expected_property_number != i32::try_from(properties.len())? {
+                return Err(format!("Tandoor reported {} properties but {} were returned.", expected_property_number, properties.len()).into());
+            }
+        }
+
+        Ok(properties
+            .into_iter()
+            .filter_map(|property| InternalTandoorProperty::try_from(property).ok())
+            .collect())
+    }
+
+    /// Creates a property type in the Tandoor instance.
+    /// ### Parameters
+    /// - property: The property type to create.
+    /// ### Remarks
+    /// The request is sent only once, as retrying a request Tandoor already applied would create a duplicate property type.
+    pub async fn create_property(&self, property: &ApiTandoorPropertyRequest) -> Result<(), Box<dyn Error>> {
+        let url = format!("{}{}", self.api_base_url, self.version.property_type_endpoint());
+        debug!("Calling {} to create property {}", url, property.name);
+        let _write_slot = self.write_slots.acquire().await?;
+        let request = self.client.post(url)
+            .bearer_auth(&self.api_key)
+            .json(property);
+        request.send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Gets all foods of the Tandoor instance
+    /// ### Parameters
+    /// - updated_since: If set only foods modified on or after this day are returned.
+    /// ### Returns
+    /// All foods that were returned by the Tandoor API together with the foods that could not be read.
+    /// ### Remarks
+    /// Tandoor filters by the modification time itself if the instance supports it. The foods are filtered
+    /// again locally as older instances ignore the filter. Foods without a modification time are left out and
+    /// an error is returned if Tandoor does not report the modification time of any food.
+    pub async fn get_foods(&self, updated_since: Option<NaiveDate>) -> Result<LoadedFoods, Box<dyn Error>> {
+        let mut url = format!("{}{}", self.api_base_url, self.version.food_endpoint());
+        if let Some(updated_since) = updated_since {
+            url = format!("{}?updated_at={}", url, updated_since);
+        }
+        let (mut foods, mut expected_food_number) = self
+            .get_all_pages(url, |body| self.version.parse_foods(body))
+            .await?;
+
+        if let Some(updated_since) = updated_since {
+            // Foods filtered locally were still counted by Tandoor.
+            let filtered_foods = i32::try_from(retain_updated_since(&mut foods, updated_since)?)?;
+            expected_food_number = expected_food_number.map(|expected| expected - filtered_foods);
+        }
+
+        let mut loaded_foods = LoadedFoods {
+            foods: Vec::new(),
+            skipped: Vec::new(),
+            expected: expected_food_number,
+        };
+        for food in foods {
+            match food {
+                Ok(food) => {
+                    let (id, name) = (food.id, food.name.to_string());
+                    match InternalTandoorFood::try_from(food) {
+                        Ok(food) => loaded_foods.foods.push(food),
+                        Err(_) => loaded_foods.skipped.push(SkippedElement {
+                            id: Some(id),
+                            name: Some(name),
+                            reason: "The food could not be converted.".to_string(),
+                        }),
+                    }
+                }
+                Err(skipped) => loaded_foods.skipped.push(skipped),
+            }
+        }
+        Ok(loaded_foods)
+    }
+
+    /// Updates the food in the Tandoor database
+    /// ### Parameters
+    /// - food: The food data that should be sent to the API
+    /// - food_id: The id of the food that should be updated with the data given by 'food' parameter
+    pub async fn update_food(&self, food: &ApiTandoorFood, food_id: &i32) -> Result<(), Box<dyn Error>> {
+        let url = format!("{}{}{}/", self.api_base_url, self.version.food_endpoint(), food_id);
+        debug!("Calling {} to update food {}", url, food.name);
+        let body = self.version.serialize_food(food)?;
+        let _write_slot = self.write_slots.acquire().await?;
+        let request = self.client.patch(url)
+            .bearer_auth(&self.api_key)
+            .json(&body);
+        self.retry_policy.send(request)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Sets the FDC ID of a food in the Tandoor database without changing anything else.
+    /// ### Parameters
+    /// - food_id: The id of the food.
+    /// - fdc_id: The FDC ID to set.
+    pub async fn update_fdc_id(&self, food_id: &i32, fdc_id: &i32) -> Result<(), Box<dyn Error>> {
+        let url = format!("{}{}{}/", self.api_base_url, self.version.food_endpoint(), food_id);
+        debug!("Calling {} to set the FDC ID of food {} to {}", url, food_id, fdc_id);
+        let _write_slot = self.write_slots.acquire().await?;
+        let request = self.client.patch(url)
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({ "fdc_id": fdc_id }));
+        self.retry_policy.send(request)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Finds a unit of the Tandoor instance by its name.
+    /// ### Parameters
+    /// - name: The name of the unit (e.g. g). Case is ignored.
+    /// ### Returns
+    /// The unit or None if the Tandoor instance does not have a unit with that name.
+    pub async fn find_unit(&self, name: &str) -> Result<Option<ApiTandoorUnit>, Box<dyn Error>> {
+        let url = Url::parse_with_params(&format!("{}{}", self.api_base_url, self.version.unit_endpoint()), [("query", name)])?;
+        let (units, _) = self
+            .get_all_pages(url.to_string(), |body| self.version.parse_units(body))
+            .await?;
+        Ok(units.into_iter().find(|unit| unit.name.eq_ignore_ascii_case(name)))
+    }
+
+    /// Gets all units of the Tandoor instance.
+    pub async fn get_units(&self) -> Result<Vec<ApiTandoorUnit>, Box<dyn Error>> {
+        let url = format!("{}{}", self.api_base_url, self.version.unit_endpoint());
+        let (units, _) = self
+            .get_all_pages(url, |body| self.version.parse_units(body))
+            .await?;
+        Ok(units)
+    }
+
+    /// Creates a unit in the Tandoor instance.
+    /// ### Parameters
+    /// - name: The name of the unit.
+    /// ### Returns
+    /// The created unit.
+    /// ### Remarks
+    /// The request is sent only once, as retrying a request Tandoor already applied would create the unit twice.
+    pub async fn create_unit(&self, name: &str) -> Result<ApiTandoorUnit, Box<dyn Error>> {
+        let url = format!("{}{}", self.api_base_url, self.version.unit_endpoint());
+        debug!("Calling {} to create unit {}", url, name);
+        let _write_slot = self.write_slots.acquire().await?;
+        let request = self.client.post(url)
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({ "name": name }));
+        let body = request.send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Gets the unit conversions of a food.
+    /// ### Parameters
+    /// - food_id: The id of the food.
+    /// ### Returns
+    /// All unit conversions Tandoor has for the food.
+    pub async fn get_unit_conversions(&self, food_id: &i32) -> Result<Vec<ApiTandoorUnitConversion>, Box<dyn Error>> {
+        let url = format!("{}{}?food_id={}", self.api_base_url, self.version.unit_conversion_endpoint(), food_id);
+        let (conversions, _) = self
+            .get_all_pages(url, |body| self.version.parse_unit_conversions(body))
+            .await?;
+        Ok(conversions)
+    }
+
+    /// Creates a unit conversion in the Tandoor instance.
+    /// ### Parameters
+    /// - conversion: The conversion to create.
+    /// ### Remarks
+    /// The request is sent only once, as retrying a request Tandoor already applied would create a duplicate conversion.
+    pub async fn create_unit_conversion(&self, conversion: &ApiTandoorUnitConversion) -> Result<(), Box<dyn Error>> {
+        let url = format!("{}{}", self.api_base_url, self.version.unit_conversion_endpoint());
+        debug!("Calling {} to create a conversion from {} to {}", url, conversion.base_unit.name, conversion.converted_unit.name);
+        let _write_slot = self.write_slots.acquire().await?;
+        let request = self.client.post(url)
+            .bearer_auth(&self.api_key)
+            .json(conversion);
+        request.send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Updates a unit conversion in the Tandoor instance.
+    /// ### Parameters
+    /// - conversion_id: The id of the conversion that should be updated.
+    /// - conversion: The new values of the conversion.
+    pub async fn update_unit_conversion(&self, conversion_id: &i32, conversion: &ApiTandoorUnitConversion) -> Result<(), Box<dyn Error>> {
+        let url = format!("{}{}{}/", self.api_base_url, self.version.unit_conversion_endpoint(), conversion_id);
+        debug!("Calling {} to update the conversion from {} to {}", url, conversion.base_unit.name, conversion.converted_unit.name);
+        let _write_slot = self.write_slots.acquire().await?;
+        let request = self.client.patch(url)
+            .bearer_auth(&self.api_key)
+            .json(conversion);
+        self.retry_policy.send(request)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Loads all pages of a list endpoint by following the `next` URL.
+    /// ### Parameters
+    /// - url: The URL of the first page.
+    /// - parse: Function parsing the body of a single page.
+    /// ### Returns
+    /// Tuple of all elements and the total number of elements reported by the API (if any).
+    async fn get_all_pages<T, F>(&self, url: String, parse: F) -> Result<(Vec<T>, Option<i32>), Box<dyn Error>>
+    where
+        F: Fn(&str) -> Result<TandoorPage<T>, Box<dyn Error>>,
+    {
+        let mut current_url = url;
+        let mut elements: Vec<T> = Vec::new();
+        let mut expected_number: Option<i32> = None;
+        loop {
+            trace!("Loading page by calling {}", current_url);
+            let request = self.client.get(current_url)
+                .bearer_auth(&self.api_key);
+            let response = self.retry_policy.send(request)
+                .await?
+                .error_for_status()?;
+
+            let body = response.text().await?;
+            trace!("Retrieved page from Tandoor: \n {}", body);
+            let page = parse(&body)?;
+            elements.extend(page.results);
+            if page.count.is_some() {
+                expected_number = page.count;
+            }
+
+            if let Some(next_url) = page.next {
+                current_url = next_url;
+                debug!("Loaded {} elements.", elements.len());
+            } else {
+                break;
+            }
+        }
+        Ok((elements, expected_number))
+    }
+}
+
+/// Keeps only the foods modified on or after the given day.
+/// ### Parameters
+/// - foods: The loaded foods. Foods that could not be read are kept, so they are still reported.
+/// - updated_since: The day.
+/// ### Returns
+/// The number of foods that were left out or an error if no food has a modification time.
+/// ### Remarks
+/// Foods without (a readable) modification time are left out with a warning, as it is unknown whether they were modified.
+fn retain_updated_since(foods: &mut Vec<Result<ApiTandoorFood, SkippedElement>>, updated_since: NaiveDate) -> Result<usize, Box<dyn Error>> {
+    let has_modification_times = foods.iter().flatten().any(|food| food.updated_at.is_some());
+    if !has_modification_times && foods.iter().flatten().next().is_some() {
+        return Err("Tandoor does not report when foods were modified, so foods cannot be selected by their modification time.".into());
+    }
+    let number_of_foods = foods.len();
+    foods.retain(|food| match food {
+        Ok(food) => match is_updated_since(food, updated_since) {
+            Some(is_updated) => is_updated,
+            None => {
+                warn!("Tandoor does not report when food {} was modified. The food is left out.", food.name);
+                false
+            }
+        },
+        Err(_) => true,
+    });
+    Ok(number_of_foods - foods.len())
+}
+
+/// Checks whether a food was modified on or after the given day.
+/// ### Returns
+/// Whether the food was modified or None if the food has no (readable) modification time.
+fn is_updated_since(food: &ApiTandoorFood, updated_since: NaiveDate) -> Option<bool> {
+    let updated_at = DateTime::parse_from_rfc3339(food.updated_at.as_deref()?).ok()?;
+    Some(updated_at.with_timezone(&Utc).date_naive() >= updated_since)
+}
+
+/// Builds the URL of the API root from the configured Tandoor URL.
+/// Plain hosts (e.g. "localhost:8080") are still supported by defaulting to http.
+fn api_base_url(tandoor_url: &str) -> String {
+    let tandoor_url = tandoor_url.trim_end_matches('/');
+    if tandoor_url.contains("://") {
+        format!("{}/api/", tandoor_url)
+    } else {
+        format!("http://{}/api/", tandoor_url)
+    }
+}
+
+/// Detects the API version of the Tandoor instance.
+/// ### Parameters
+/// - client: The client used for any http requests
+/// - api_base_url: The URL of the API root of the Tandoor instance.
+/// - api_key: The API key to interact with the Tandoor API
+/// ### Returns
+/// The detected version or an error if the version could not be detected.
+/// ### Remarks
+/// The API root lists all available endpoints. The versions differ in the name of the property endpoint,
+/// which is used to distinguish them. If the root cannot be read, the property endpoints are probed directly.
+async fn detect_version(client: &Client, api_base_url: &str, api_key: &str) -> Result<Box<dyn TandoorApiVersion>, Box<dyn Error>> {
+    trace!("Detecting Tandoor API version by calling {}", api_base_url);
+    let response = client.get(api_base_url)
+        .bearer_auth(api_key)
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        let body = response.text().await?;
+        if let Ok(api_root) = serde_json::from_str::<HashMap<String, serde_json::Value>>(&body) {
+            if let Some(version) = supported_versions()
+                .into_iter()
+                .find(|v| api_root.contains_key(v.property_type_endpoint().trim_end_matches('/'))) {
+                debug!("Detected Tandoor API version {} from the API root.", version.name());
+                return Ok(version);
+            }
+        }
+        debug!("The API root did not list a known property endpoint.");
+    } else {
+        debug!("The API root returned status {}.", response.status());
+    }
+
+    // Fall back to probing the property endpoints of the known versions.
+    for version in supported_versions() {
+        let probe_url = format!("{}{}", api_base_url, version.property_type_endpoint());
+        trace!("Probing {}", probe_url);
+        let response = client.get(probe_url)
+            .bearer_auth(api_key)
+            .send()
+            .await?;
+        if response.status().is_success() {
+            debug!("Detected Tandoor API version {} by probing.", version.name());
+            return Ok(version);
+        }
+    }
+
+    Err("None of the known property endpoints could be reached. Set \"tandoorVersion\" in the appsettings to skip the version detection.".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn food(name: &str, updated_at: Option<&str>) -> Result<ApiTandoorFood, SkippedElement> {
+        Ok(ApiTandoorFood {
+            id: 1,
+            name: name.to_string(),
+            fdc_id: None,
+            properties: Vec::new(),
+            url: None,
+            properties_food_amount: None,
+            properties_food_unit: None,
+            updated_at: updated_at.map(str::to_string),
+        })
+    }
+
+    fn day(text: &str) -> NaiveDate {
+        text.parse().unwrap()
+    }
+
+    fn names(foods: &[Result<ApiTandoorFood, SkippedElement>]) -> Vec<&str> {
+        foods.iter().flatten().map(|food| food.name.as_str()).collect()
+    }
+
+    #[test]
+    fn foods_modified_before_the_day_are_left_out() {
+        let mut foods = vec![
+            food("Butter", Some("2026-03-01T10:00:00+01:00")),
+            food("Ghee", Some("2026-02-28T23:59:00+00:00")),
+            food("Milk", Some("2026-02-28T23:30:00-01:00")),
+        ];
+        assert_eq!(retain_updated_since(&mut foods, day("2026-03-01")).unwrap(), 1);
+        assert_eq!(names(&foods), vec!["Butter", "Milk"]);
+    }
+
+    #[test]
+    fn foods_without_modification_time_are_left_out() {
+        let mut foods = vec![food("Butter", Some("2026-03-01T10:00:00Z")), food("Ghee", None), food("Milk", Some("yesterday"))];
+        assert_eq!(retain_updated_since(&mut foods, day("2026-01-01")).unwrap(), 2);
+        assert_eq!(names(&foods), vec!["Butter"]);
+    }
+
+    #[test]
+    fn selecting_by_modification_time_fails_if_tandoor_does_not_report_it() {
+        let mut foods = vec![food("Butter", None), food("Ghee", None)];
+        assert!(retain_updated_since(&mut foods, day("2026-01-01")).is_err());
+        assert!(retain_updated_since(&mut Vec::new(), day("2026-01-01")).is_ok());
+    }
+
+    #[test]
+    fn urls_without_scheme_use_http() {
+        assert_eq!(api_base_url("localhost:8080"), "http://localhost:8080/api/");
+    }
+
+    #[test]
+    fn urls_keep_their_scheme() {
+        assert_eq!(api_base_url("https://tandoor.example.com"), "https://tandoor.example.com/api/");
+        assert_eq!(api_base_url("http://192.168.1.10:8080"), "http://192.168.1.10:8080/api/");
+    }
+
+    #[test]
+    fn urls_keep_their_sub_path() {
+        assert_eq!(api_base_url("example.com/tandoor"), "http://example.com/tandoor/api/");
+        assert_eq!(api_base_url("https://example.com/tandoor"), "https://example.com/tandoor/api/");
+    }
+
+    #[test]
+    fn trailing_slashes_are_removed() {
+        assert_eq!(api_base_url("https://example.com/tandoor/"), "https://example.com/tandoor/api/");
+        assert_eq!(api_base_url("localhost:8080//"), "http://localhost:8080/api/");
+    }
+}
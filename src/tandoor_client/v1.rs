@@ -0,0 +1,70 @@
+//! Layout and models of the Tandoor v1 API.
+use std::error::Error;
+use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
+use crate::models::tandoor::api_tandoor_food_response::ApiFoodResponse;
+use crate::models::tandoor::api_tandoor_property::ApiTandoorProperty;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+use crate::models::tandoor::api_tandoor_unit_conversion::ApiTandoorUnitConversion;
+use crate::models::tandoor::api_tandoor_unit_response::ApiUnitResponse;
+use crate::tandoor_client::{parse_elements, SkippedElement, TandoorApiVersion, TandoorPage};
+
+/// Tandoor v1 returns all property types and unit conversions at once and names the property type endpoint `food-property-type`.
+pub struct TandoorV1;
+
+impl TandoorApiVersion for TandoorV1 {
+    fn name(&self) -> &'static str {
+        "v1"
+    }
+
+    fn food_endpoint(&self) -> &'static str {
+        "food/"
+    }
+
+    fn property_type_endpoint(&self) -> &'static str {
+        "food-property-type/"
+    }
+
+    fn unit_endpoint(&self) -> &'static str {
+        "unit/"
+    }
+
+    fn unit_conversion_endpoint(&self) -> &'static str {
+        "unit-conversion/"
+    }
+
+    fn parse_property_types(&self, body: &str) -> Result<TandoorPage<ApiTandoorProperty>, Box<dyn Error>> {
+        let properties: Vec<ApiTandoorProperty> = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: None,
+            next: None,
+            results: properties,
+        })
+    }
+
+    fn parse_foods(&self, body: &str) -> Result<TandoorPage<Result<ApiTandoorFood, SkippedElement>>, Box<dyn Error>> {
+        let response: ApiFoodResponse = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: Some(response.count),
+            next: response.next,
+            results: parse_elements(response.results),
+        })
+    }
+
+    fn parse_units(&self, body: &str) -> Result<TandoorPage<ApiTandoorUnit>, Box<dyn Error>> {
+        let response: ApiUnitResponse = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: Some(response.count),
+            next: response.next,
+            results: response.results,
+        })
+    }
+
+    fn parse_unit_conversions(&self, body: &str) -> Result<TandoorPage<ApiTandoorUnitConversion>, Box<dyn Error>> {
+        let conversions: Vec<ApiTandoorUnitConversion> = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: None,
+            next: None,
+            results: conversions,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+//! Layout and models of the Tandoor v2 API.
+use std::error::Error;
+use crate::models::tandoor::api_tandoor_food::ApiTandoorFood;
+use crate::models::tandoor::api_tandoor_food_response::ApiFoodResponse;
+use crate::models::tandoor::api_tandoor_property::ApiTandoorProperty;
+use crate::models::tandoor::api_tandoor_property_response::ApiPropertyTypeResponse;
+use crate::models::tandoor::api_tandoor_unit::ApiTandoorUnit;
+use crate::models::tandoor::api_tandoor_unit_conversion::ApiTandoorUnitConversion;
+use crate::models::tandoor::api_tandoor_unit_conversion_response::ApiUnitConversionResponse;
+use crate::models::tandoor::api_tandoor_unit_response::ApiUnitResponse;
+use crate::tandoor_client::{parse_elements, SkippedElement, TandoorApiVersion, TandoorPage};
+
+/// Tandoor v2 paginates property types and names the endpoint `property-type`.
+pub struct TandoorV2;
+
+impl TandoorApiVersion for TandoorV2 {
+    fn name(&self) -> &'static str {
+        "v2"
+    }
+
+    fn food_endpoint(&self) -> &'static str {
+        "food/"
+    }
+
+    fn property_type_endpoint(&self) -> &'static str {
+        "property-type/"
+    }
+
+    fn unit_endpoint(&self) -> &'static str {
+        "unit/"
+    }
+
+    fn unit_conversion_endpoint(&self) -> &'static str {
+        "unit-conversion/"
+    }
+
+    fn parse_property_types(&self, body: &str) -> Result<TandoorPage<ApiTandoorProperty>, Box<dyn Error>> {
+        let response: ApiPropertyTypeResponse = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: Some(response.count),
+            next: response.next,
+            results: response.results,
+        })
+    }
+
+    fn parse_foods(&self, body: &str) -> Result<TandoorPage<Result<ApiTandoorFood, SkippedElement>>, Box<dyn Error>> {
+        let response: ApiFoodResponse = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: Some(response.count),
+            next: response.next,
+            results: parse_elements(response.results),
+        })
+    }
+
+    fn parse_units(&self, body: &str) -> Result<TandoorPage<ApiTandoorUnit>, Box<dyn Error>> {
+        let response: ApiUnitResponse = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: Some(response.count),
+            next: response.next,
+            results: response.results,
+        })
+    }
+
+    fn parse_unit_conversions(&self, body: &str) -> Result<TandoorPage<ApiTandoorUnitConversion>, Box<dyn Error>> {
+        let response: ApiUnitConversionResponse = serde_json::from_str(body)?;
+        Ok(TandoorPage {
+            count: Some(response.count),
+            next: response.next,
+            results: response.results,
+        })
+    }
+}
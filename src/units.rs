@@ -0,0 +1,132 @@
+//! Converts nutrient amounts between the units used by the FDC database and the units of Tandoor properties.
+//!
+//! Mass (kg, g, mg, µg) and energy (kJ, kcal) can always be converted. International units (IU) measure the
+//! biological activity of a nutrient, so they can only be converted for the vitamins they are defined for.
+
+/// The name of the unit grams.
+pub const GRAM: &str = "g";
+
+/// A unit a nutrient amount can be given in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    /// A mass with the number of micrograms it equals.
+    Mass(f64),
+    /// An energy with the number of kilojoules it equals.
+    Energy(f64),
+    /// International units.
+    International,
+}
+
+/// Micrograms equal to one international unit, per FDC nutrient ID.
+const MICROGRAMS_PER_IU: &[(i32, f64)] = &[
+    (1104, 0.3), // Vitamin A, IU (as retinol)
+    (1105, 0.3), // Retinol
+    (1106, 0.3), // Vitamin A, RAE
+    (1110, 0.025), // Vitamin D (D2 + D3), International Units
+    (1114, 0.025), // Vitamin D (D2 + D3)
+    (1109, 670.0), // Vitamin E (alpha-tocopherol), natural form
+];
+
+/// Converts an amount from the unit of an FDC nutrient to the unit of a Tandoor property.
+/// ### Parameters
+/// - amount: The amount in the unit of the FDC nutrient.
+/// - from: The unit of the FDC nutrient (e.g. MG).
+/// - to: The unit of the Tandoor property (e.g. g).
+/// - nutrient_id: The ID of the FDC nutrient, needed to convert international units.
+/// ### Returns
+/// The amount in the unit of the Tandoor property or None if there is no defined conversion between the units.
+/// ### Remarks
+/// Units that are not known are only accepted if they are written the same way.
+pub fn convert(amount: f32, from: &str, to: &str, nutrient_id: i32) -> Option<f32> {
+    if normalize(from) == normalize(to) {
+        return Some(amount);
+    }
+    let factor = match (parse(from)?, parse(to)?) {
+        (Unit::Mass(from), Unit::Mass(to)) => from / to,
+        (Unit::Energy(from), Unit::Energy(to)) => from / to,
+        (Unit::International, Unit::Mass(to)) => micrograms_per_iu(nutrient_id)? / to,
+        (Unit::Mass(from), Unit::International) => from / micrograms_per_iu(nutrient_id)?,
+        _ => return None,
+    };
+    Some((amount as f64 * factor) as f32)
+}
+
+/// Gets the micrograms equal to one international unit of a nutrient.
+fn micrograms_per_iu(nutrient_id: i32) -> Option<f64> {
+    MICROGRAMS_PER_IU.iter().find(|(id, _)| *id == nutrient_id).map(|(_, micrograms)| *micrograms)
+}
+
+/// Brings the different spellings of a unit (e.g. MG, mg or mg_ATE) into one form.
+fn normalize(unit: &str) -> String {
+    let unit = unit.trim().to_lowercase();
+    match unit.as_str() {
+        // Milligrams of alpha-tocopherol equivalents are still milligrams.
+        "mg_ate" => "mg".to_string(),
+        "ug" | "μg" | "mcg" => "µg".to_string(),
+        _ => unit,
+    }
+}
+
+/// Parses a unit. Returns None for units that cannot be converted.
+fn parse(unit: &str) -> Option<Unit> {
+    match normalize(unit).as_str() {
+        "kg" => Some(Unit::Mass(1_000_000_000.0)),
+        "g" => Some(Unit::Mass(1_000_000.0)),
+        "mg" => Some(Unit::Mass(1_000.0)),
+        "µg" => Some(Unit::Mass(1.0)),
+        "kj" => Some(Unit::Energy(1.0)),
+        "kcal" => Some(Unit::Energy(4.184)),
+        "iu" => Some(Unit::International),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_converts(amount: f32, from: &str, to: &str, nutrient_id: i32, expected: f32) {
+        let converted = convert(amount, from, to, nutrient_id).expect("the units should be convertible");
+        assert!((converted - expected).abs() <= expected.abs() * 1e-5, "{} {} should be {} {} but was {}", amount, from, expected, to, converted);
+    }
+
+    #[test]
+    fn converts_masses() {
+        assert_converts(1500.0, "MG", "g", 1093, 1.5);
+        assert_converts(2.0, "G", "mg", 1003, 2000.0);
+        assert_converts(250.0, "UG", "mg", 1162, 0.25);
+        assert_converts(1.0, "kg", "g", 1003, 1000.0);
+    }
+
+    #[test]
+    fn converts_energies() {
+        assert_converts(100.0, "KCAL", "kJ", 1008, 418.4);
+        assert_converts(418.4, "kJ", "kcal", 1062, 100.0);
+    }
+
+    #[test]
+    fn converts_international_units_of_vitamins() {
+        // Vitamin D: 1 IU = 0.025 µg
+        assert_converts(400.0, "IU", "µg", 1110, 10.0);
+        assert_converts(10.0, "UG", "IU", 1110, 400.0);
+        // Vitamin A: 1 IU = 0.3 µg
+        assert_converts(1000.0, "IU", "µg", 1106, 300.0);
+        // Vitamin E: 1 IU = 0.67 mg
+        assert_converts(1.0, "IU", "mg", 1109, 0.67);
+    }
+
+    #[test]
+    fn accepts_different_spellings_of_a_unit() {
+        assert_converts(5.0, "mcg", "µg", 1178, 5.0);
+        assert_converts(5.0, "μg", "ug", 1178, 5.0);
+        assert_converts(3.0, "MG_ATE", "mg", 1109, 3.0);
+        assert_converts(7.0, "pieces", "PIECES", 0, 7.0);
+    }
+
+    #[test]
+    fn refuses_undefined_conversions() {
+        assert_eq!(convert(100.0, "IU", "g", 1093), None);
+        assert_eq!(convert(100.0, "KCAL", "g", 1008), None);
+        assert_eq!(convert(1.0, "piece", "g", 0), None);
+    }
+}